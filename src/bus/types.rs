@@ -2,9 +2,11 @@ use std::fmt::Debug;
 use std::sync::Arc;
 use anyhow::Result;
 use async_trait::async_trait;
-use tokio::sync::{broadcast};
+use tokio::sync::{broadcast, watch};
 use tracing::info;
-use crate::core::types::{RawNews, MarketDataRequest, MarketDataSnap, Order, Execution, PolyMarketEvent};
+use crate::core::types::{RawNews, EnrichedNews, MarketDataRequest, MarketDataSnap, Order, OrderUpdate, Execution, MarketChange, MarketSnapshotRequest, PolyMarketEvent};
+use crate::execution::orders::OpenOrders;
+use crate::strategy::types::Decision;
 
 // ---------- Topic trait (broadcast semantics) ----------
 #[async_trait::async_trait]
@@ -46,11 +48,18 @@ impl<T: Debug + Clone + Send + Sync + 'static> Topic<T> for BroadcastTopic<T> {
 #[derive(Clone)]
 pub struct Bus {
     pub raw_news: Arc<dyn Topic<RawNews>>,
+    pub enriched_news: Arc<dyn Topic<EnrichedNews>>,
     pub polymarket_events: Arc<dyn Topic<PolyMarketEvent>>,
+    pub market_changes: Arc<dyn Topic<MarketChange>>,
+    pub market_snapshot_request: Arc<dyn Topic<MarketSnapshotRequest>>,
     pub market_data_request: Arc<dyn Topic<MarketDataRequest>>,
     pub market_data: Arc<dyn Topic<MarketDataSnap>>,
+    pub decisions: Arc<dyn Topic<Decision>>,
     pub orders: Arc<dyn Topic<Order>>,
+    pub order_updates: Arc<dyn Topic<OrderUpdate>>,
     pub executions: Arc<dyn Topic<Execution>>,
+    /// Latest open orders, written by the ExecutionActor. `borrow()` to read, `subscribe()` to watch.
+    pub open_orders: Arc<watch::Sender<OpenOrders>>,
 }
 
 impl Bus {
@@ -59,11 +68,18 @@ impl Bus {
 
         Self {
             raw_news: Arc::new(BroadcastTopic::<RawNews>::with_capacity(cap)),
+            enriched_news: Arc::new(BroadcastTopic::<EnrichedNews>::with_capacity(cap)),
             polymarket_events: Arc::new(BroadcastTopic::<PolyMarketEvent>::with_capacity(cap)),
+            // the first refresh adds every listed market at once
+            market_changes: Arc::new(BroadcastTopic::<MarketChange>::with_capacity(64 * cap)),
+            market_snapshot_request: Arc::new(BroadcastTopic::<MarketSnapshotRequest>::with_capacity(cap)),
             market_data_request: Arc::new(BroadcastTopic::<MarketDataRequest>::with_capacity(cap)),
             market_data: Arc::new(BroadcastTopic::<MarketDataSnap>::with_capacity(cap)),
+            decisions: Arc::new(BroadcastTopic::<Decision>::with_capacity(cap)),
             orders: Arc::new(BroadcastTopic::<Order>::with_capacity(cap)),
+            order_updates: Arc::new(BroadcastTopic::<OrderUpdate>::with_capacity(cap)),
             executions: Arc::new(BroadcastTopic::<Execution>::with_capacity(cap)),
+            open_orders: Arc::new(watch::Sender::new(OpenOrders::new())),
         }
     }
 }
\ No newline at end of file
@@ -1,7 +1,10 @@
 use serde::Deserialize;
 use anyhow::{Result, Context};
+use std::collections::HashMap;
 use std::time::Duration;
 use config::{Config, File};
+use crate::core::types::{TimeInForce, Urgency};
+use crate::text::entities::EntityKind;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppCfg {
@@ -10,6 +13,9 @@ pub struct AppCfg {
     pub rss: RssCfg,
     #[serde(rename = "financialJuice")]
     pub financial_juice: FinJuiceCfg,
+    pub text: TextCfg,
+    pub strategy: StrategyCfg,
+    pub execution: ExecutionCfg,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -70,11 +76,438 @@ pub struct FinJuiceCfg {
     pub cookie: String
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct TextCfg {
+    /// IANA zone used to cut calendar days when resolving "Dec 15", "this Friday", ...
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+    pub entities: EntitiesCfg,
+    pub dedup: DedupCfg,
+    pub stories: StoriesCfg,
+}
+fn default_timezone() -> String { "America/New_York".into() }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct DedupCfg {
+    /// How long a headline hash is remembered after it was last seen (24h-72h).
+    #[serde(with = "humantime_serde")]
+    pub ttl: Duration,
+    #[serde(default = "default_dedup_capacity")]
+    pub capacity: usize,
+    /// How often per-feed duplicate counters are logged.
+    #[serde(rename = "statsInterval", with = "humantime_serde", default = "default_dedup_stats")]
+    pub stats_interval: Duration,
+}
+fn default_dedup_capacity() -> usize { 200_000 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct StoriesCfg {
+    /// Max SimHash Hamming distance for two headlines to count as the same story.
+    #[serde(rename = "maxDistance", default = "default_story_distance")]
+    pub max_distance: u32,
+    /// How long a story stays open for confirmations after its last arrival.
+    #[serde(with = "humantime_serde", default = "default_story_window")]
+    pub window: Duration,
+}
+fn default_story_distance() -> u32 { 10 }
+fn default_story_window() -> Duration { Duration::from_secs(6 * 3600) }
+fn default_dedup_stats() -> Duration { Duration::from_secs(60) }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct EntitiesCfg {
+    /// How often dictionary files are re-read.
+    #[serde(with = "humantime_serde", default = "default_entities_reload")]
+    pub reload: Duration,
+    pub dictionaries: Vec<DictionaryCfg>,
+}
+fn default_entities_reload() -> Duration { Duration::from_secs(300) }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct DictionaryCfg {
+    pub kind: EntityKind,
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct StrategyCfg {
+    pub index: IndexCfg,
+    pub filters: FiltersCfg,
+    pub scorer: ScorerCfg,
+    pub select: SelectCfg,
+    pub probability: ProbabilityCfg,
+    pub trade: TradeCfg,
+    pub sizing: SizingCfg,
+    /// JSON lines file every decision is appended to.
+    #[serde(rename = "decisionLog", default = "default_decision_log")]
+    pub decision_log: String,
+}
+fn default_decision_log() -> String { "decisions.jsonl".to_string() }
+
+/// BM25F retrieval over market text.
+#[derive(Debug, Deserialize, Clone)]
+pub struct IndexCfg {
+    /// Term-frequency saturation.
+    #[serde(default = "default_bm25_k1")]
+    pub k1: f64,
+    /// Length normalization, 0 (none) to 1 (full).
+    #[serde(default = "default_bm25_b")]
+    pub b: f64,
+    /// How many candidates retrieval hands to the filters.
+    #[serde(default = "default_candidates")]
+    pub candidates: usize,
+    pub boosts: FieldBoostsCfg,
+}
+fn default_bm25_k1() -> f64 { 1.2 }
+fn default_bm25_b() -> f64 { 0.75 }
+fn default_candidates() -> usize { 100 }
+
+/// Per-field weights applied to term frequencies before saturation.
+#[derive(Debug, Deserialize, Clone)]
+pub struct FieldBoostsCfg {
+    pub title: f64,
+    pub description: f64,
+    pub tags: f64,
+    pub outcomes: f64,
+}
+
+/// Hard filters applied to BM25 candidates; each can be switched off on its own.
+#[derive(Debug, Deserialize, Clone)]
+pub struct FiltersCfg {
+    pub liquidity: LiquidityFilterCfg,
+    pub date: DateFilterCfg,
+    pub category: CategoryFilterCfg,
+    pub entities: EntityFilterCfg,
+}
+fn default_enabled() -> bool { true }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct LiquidityFilterCfg {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(rename = "minOpenInterest", default)]
+    pub min_open_interest: f64,
+    #[serde(rename = "minVolume24h", default)]
+    pub min_volume_24h: f64,
+    /// Widest acceptable bid/ask spread, in price units (0.05 = 5c).
+    #[serde(rename = "maxSpread", default = "default_max_spread")]
+    pub max_spread: f64,
+}
+fn default_max_spread() -> f64 { 0.10 }
+
+/// A market must resolve within [window start - early, window end + late] of some headline window.
+#[derive(Debug, Deserialize, Clone)]
+pub struct DateFilterCfg {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(rename = "earlyTolerance", with = "humantime_serde", default = "default_early_tolerance")]
+    pub early_tolerance: Duration,
+    #[serde(rename = "lateTolerance", with = "humantime_serde", default = "default_late_tolerance")]
+    pub late_tolerance: Duration,
+}
+fn default_early_tolerance() -> Duration { Duration::from_secs(24 * 3600) }
+fn default_late_tolerance() -> Duration { Duration::from_secs(31 * 24 * 3600) }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CategoryFilterCfg {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub rules: Vec<CategoryRuleCfg>,
+}
+
+/// Headlines mentioning any entity of `kinds` (or any of `entities`) only match markets whose
+/// category or tags include one of `tags`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct CategoryRuleCfg {
+    pub name: String,
+    #[serde(default)]
+    pub kinds: Vec<EntityKind>,
+    #[serde(default)]
+    pub entities: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+/// Headline and market must share an entity of one of `kinds`, when the headline has any.
+#[derive(Debug, Deserialize, Clone)]
+pub struct EntityFilterCfg {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_overlap_kinds")]
+    pub kinds: Vec<EntityKind>,
+}
+fn default_overlap_kinds() -> Vec<EntityKind> { EntityKind::ALL.to_vec() }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ScorerCfg {
+    pub weights: ScoreWeightsCfg,
+    pub norms: ScoreNormsCfg,
+}
+
+/// score = bm25 * bm25_norm + entityOverlap * entity_overlap + numberOverlap * number_overlap
+///       + timeCompat * time_compat + liquidity * liquidity_score - staleness * staleness_penalty
+#[derive(Debug, Deserialize, Clone)]
+pub struct ScoreWeightsCfg {
+    pub bm25: f64,
+    #[serde(rename = "entityOverlap")]
+    pub entity_overlap: f64,
+    #[serde(rename = "numberOverlap")]
+    pub number_overlap: f64,
+    #[serde(rename = "timeCompat")]
+    pub time_compat: f64,
+    pub liquidity: f64,
+    pub staleness: f64,
+}
+
+/// Maps each raw term onto [0, 1].
+#[derive(Debug, Deserialize, Clone)]
+pub struct ScoreNormsCfg {
+    /// Raw BM25 score that normalizes to 0.5 (bm25 / (bm25 + scale)).
+    #[serde(rename = "bm25Scale", default = "default_bm25_scale")]
+    pub bm25_scale: f64,
+    /// Relative difference under which two numbers count as equal.
+    #[serde(rename = "numberTolerance", default = "default_number_tolerance")]
+    pub number_tolerance: f64,
+    /// time_compat falls to 1/e this far outside the nearest headline window.
+    #[serde(rename = "timeDecay", with = "humantime_serde", default = "default_time_decay")]
+    pub time_decay: Duration,
+    /// time_compat when the headline has no dates or the market no end date.
+    #[serde(rename = "timeNeutral", default = "default_time_neutral")]
+    pub time_neutral: f64,
+    /// 24h volume that scores 0.5.
+    #[serde(rename = "volumeScale", default = "default_volume_scale")]
+    pub volume_scale: f64,
+    /// Open interest that scores 0.5.
+    #[serde(rename = "openInterestScale", default = "default_open_interest_scale")]
+    pub open_interest_scale: f64,
+    /// Spread that scores 0; tighter spreads score linearly up to 1.
+    #[serde(rename = "spreadScale", default = "default_spread_scale")]
+    pub spread_scale: f64,
+    /// Market age at which the staleness penalty is full.
+    #[serde(rename = "staleAfter", with = "humantime_serde", default = "default_stale_after")]
+    pub stale_after: Duration,
+}
+fn default_bm25_scale() -> f64 { 5.0 }
+fn default_number_tolerance() -> f64 { 0.01 }
+fn default_time_decay() -> Duration { Duration::from_secs(14 * 24 * 3600) }
+fn default_time_neutral() -> f64 { 0.5 }
+fn default_volume_scale() -> f64 { 10_000.0 }
+fn default_open_interest_scale() -> f64 { 50_000.0 }
+fn default_spread_scale() -> f64 { 0.10 }
+fn default_stale_after() -> Duration { Duration::from_secs(180 * 24 * 3600) }
+
+/// Top-K selection: threshold filters, then Maximal Marginal Relevance re-ranking.
+#[derive(Debug, Deserialize, Clone)]
+pub struct SelectCfg {
+    #[serde(rename = "minBm25", default = "default_min_bm25")]
+    pub min_bm25: f64,
+    #[serde(rename = "minEntityOverlap", default = "default_min_entity_overlap")]
+    pub min_entity_overlap: f64,
+    /// mmr = lambda * score - (1 - lambda) * max similarity to already selected markets.
+    #[serde(default = "default_mmr_lambda")]
+    pub lambda: f64,
+    #[serde(default = "default_top_k")]
+    pub k: usize,
+    /// Similarity floor between two markets of the same event.
+    #[serde(rename = "sameEventSimilarity", default = "default_same_event_similarity")]
+    pub same_event_similarity: f64,
+    /// Most markets selected from one event; MMR alone only reorders siblings.
+    #[serde(rename = "maxPerEvent", default = "default_max_per_event")]
+    pub max_per_event: usize,
+}
+fn default_min_bm25() -> f64 { 0.3 }
+fn default_min_entity_overlap() -> f64 { 0.2 }
+fn default_mmr_lambda() -> f64 { 0.7 }
+fn default_top_k() -> usize { 5 }
+fn default_same_event_similarity() -> f64 { 0.8 }
+fn default_max_per_event() -> usize { 2 }
+
+/// Score -> belief mapping, with per-category overrides keyed by market category or tag.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ProbabilityCfg {
+    pub default: CalibrationCfg,
+    #[serde(default)]
+    pub categories: HashMap<String, CalibrationCfg>,
+}
+
+/// p = 0.5 + lambda * (sigmoid(a + b * score) - 0.5)
+#[derive(Debug, Deserialize, Clone, Copy)]
+pub struct CalibrationCfg {
+    pub a: f64,
+    pub b: f64,
+    pub lambda: f64,
+}
+
+/// Belief vs quote: trade when |p - mid| > tau + spread / 2 + feeBuffer.
+#[derive(Debug, Deserialize, Clone)]
+pub struct TradeCfg {
+    #[serde(default = "default_tau")]
+    pub tau: f64,
+    /// Expected fee per contract, in price units.
+    #[serde(rename = "feeBuffer", default)]
+    pub fee_buffer: f64,
+    /// How long a signal waits for its quote before it is dropped.
+    #[serde(rename = "quoteTimeout", with = "humantime_serde", default = "default_quote_timeout")]
+    pub quote_timeout: Duration,
+    /// `gtd` orders not filled within this are cancelled by the venue.
+    #[serde(rename = "orderTtl", with = "humantime_serde", default = "default_order_ttl")]
+    pub order_ttl: Duration,
+    /// `gtd`, or `gtc` to rest until filled, `fok` or `fak` to never rest.
+    #[serde(rename = "timeInForce", default = "default_time_in_force")]
+    pub time_in_force: TimeInForce,
+    /// Carried on order intents.
+    #[serde(default = "default_urgency")]
+    pub urgency: Urgency,
+}
+fn default_tau() -> f64 { 0.015 }
+fn default_quote_timeout() -> Duration { Duration::from_secs(2) }
+fn default_order_ttl() -> Duration { Duration::from_secs(30) }
+fn default_time_in_force() -> TimeInForce { TimeInForce::Gtd }
+fn default_urgency() -> Urgency { Urgency::Normal }
+
+/// Fractional Kelly with caps. Caps are fractions of bankroll, except `maxDepthFraction`
+/// which is a fraction of the contracts visible at the touch.
+#[derive(Debug, Deserialize, Clone)]
+pub struct SizingCfg {
+    /// κ applied to the full Kelly fraction.
+    #[serde(default = "default_kelly")]
+    pub kelly: f64,
+    /// Capital in USD.
+    pub bankroll: f64,
+    #[serde(rename = "maxMarketFraction", default = "default_max_market_fraction")]
+    pub max_market_fraction: f64,
+    #[serde(rename = "maxEventFraction", default = "default_max_event_fraction")]
+    pub max_event_fraction: f64,
+    #[serde(rename = "maxGrossFraction", default = "default_max_gross_fraction")]
+    pub max_gross_fraction: f64,
+    #[serde(rename = "maxDepthFraction", default = "default_max_depth_fraction")]
+    pub max_depth_fraction: f64,
+}
+fn default_kelly() -> f64 { 0.25 }
+fn default_max_market_fraction() -> f64 { 0.05 }
+fn default_max_event_fraction() -> f64 { 0.10 }
+fn default_max_gross_fraction() -> f64 { 0.50 }
+fn default_max_depth_fraction() -> f64 { 0.25 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ExecutionCfg {
+    pub mode: ExecutionMode,
+    #[serde(default)]
+    pub fees: FeesCfg,
+    pub policy: PolicyCfg,
+    pub slicing: SlicingCfg,
+    pub paper: PaperCfg,
+    /// Required when `mode: clob` or `mode: mock`.
+    #[serde(default)]
+    pub clob: Option<ClobCfg>,
+    /// Where `mode: mock` finds `ingestor mock-clob`.
+    #[serde(rename = "mockUrl", default = "default_mock_url")]
+    pub mock_url: String,
+}
+fn default_mock_url() -> String { "http://127.0.0.1:8081".to_string() }
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ExecutionMode {
+    /// Simulated fills against the latest market data.
+    Paper,
+    /// Signed orders against `ingestor mock-clob`, using the `clob` credentials.
+    Mock,
+    /// Signed orders on the Polymarket CLOB.
+    Clob,
+}
+
+/// Limit-first: quote inside the spread, take only when the edge left after crossing clears
+/// `crossThreshold`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct PolicyCfg {
+    /// Edge per contract required after half the spread and the taker fee.
+    #[serde(rename = "crossThreshold", default = "default_cross_threshold")]
+    pub cross_threshold: f64,
+    /// Venue price increment.
+    #[serde(default = "default_tick")]
+    pub tick: f64,
+}
+fn default_cross_threshold() -> f64 { 0.01 }
+fn default_tick() -> f64 { 0.01 }
+
+/// Child orders of a larger order, and how often they may be re-priced.
+#[derive(Debug, Deserialize, Clone)]
+pub struct SlicingCfg {
+    /// Child size as a fraction of the contracts at the touch it trades against or joins.
+    #[serde(rename = "maxDepthFraction", default = "default_slice_depth_fraction")]
+    pub max_depth_fraction: f64,
+    /// Children are at least this many contracts, unless less remains.
+    #[serde(rename = "minChildSize", default = "default_min_child_size")]
+    pub min_child_size: f64,
+    /// Least time between sending children or replacements for one order.
+    #[serde(rename = "replaceInterval", with = "humantime_serde", default = "default_replace_interval")]
+    pub replace_interval: Duration,
+}
+fn default_slice_depth_fraction() -> f64 { 0.5 }
+fn default_min_child_size() -> f64 { 5.0 }
+fn default_replace_interval() -> Duration { Duration::from_secs(1) }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PaperCfg {
+    /// Starting USDC.
+    #[serde(default = "default_paper_balance")]
+    pub balance: f64,
+}
+fn default_paper_balance() -> f64 { 1000.0 }
+
+/// Polymarket CLOB access. Empty secrets fall back to the POLY_PRIVATE_KEY, POLY_API_KEY,
+/// POLY_API_SECRET and POLY_PASSPHRASE environment variables.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ClobCfg {
+    #[serde(default = "default_clob_url")]
+    pub url: String,
+    #[serde(rename = "chainId", default = "default_chain_id")]
+    pub chain_id: u64,
+    /// CTF exchange contract the orders are signed for.
+    #[serde(default = "default_exchange")]
+    pub exchange: String,
+    #[serde(rename = "negRiskExchange", default = "default_neg_risk_exchange")]
+    pub neg_risk_exchange: String,
+    /// Proxy or safe wallet holding the funds; the signer's own address when empty.
+    #[serde(default)]
+    pub funder: String,
+    /// 0 EOA, 1 Polymarket proxy, 2 Gnosis safe. 1 and 2 need `funder`.
+    #[serde(rename = "signatureType", default)]
+    pub signature_type: u8,
+    #[serde(rename = "privateKey", default)]
+    pub private_key: String,
+    #[serde(rename = "apiKey", default)]
+    pub api_key: String,
+    #[serde(rename = "apiSecret", default)]
+    pub api_secret: String,
+    #[serde(rename = "apiPassphrase", default)]
+    pub api_passphrase: String,
+    /// How often live orders are polled for fills.
+    #[serde(rename = "pollInterval", with = "humantime_serde", default = "default_poll_interval")]
+    pub poll_interval: Duration,
+}
+fn default_clob_url() -> String { "https://clob.polymarket.com".to_string() }
+fn default_chain_id() -> u64 { 137 }
+fn default_exchange() -> String { "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E".to_string() }
+fn default_neg_risk_exchange() -> String { "0xC5d563A36AE78145C45a50134d48A1215220f80a".to_string() }
+fn default_poll_interval() -> Duration { Duration::from_secs(1) }
+
+/// Polymarket style: fee = bps / 10000 * min(price, 1 - price) * size.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct FeesCfg {
+    #[serde(rename = "makerBps", default)]
+    pub maker_bps: f64,
+    #[serde(rename = "takerBps", default)]
+    pub taker_bps: f64,
+}
+
+pub const CALIBRATION_PATH: &str = "calibration.yml";
 
 impl AppCfg {
     pub fn load(path: &str) -> Result<Self> {
         let cfg = Config::builder()
             .add_source(File::with_name(path))
+            // fitted by `calibrate`
+            .add_source(File::with_name(CALIBRATION_PATH).required(false))
             .build()
             .context("building config")?;
 
@@ -89,6 +522,81 @@ impl AppCfg {
         anyhow::ensure!(self.rss.concurrency > 0, "rss.concurrency must be > 0");
         anyhow::ensure!(!self.rss.feeds.is_empty(), "rss.feeds must not be empty");
         anyhow::ensure!(!self.financial_juice.baseUrl.is_empty(), "financialJuice.baseUrl required in non-dev env");
+        anyhow::ensure!(!self.text.entities.dictionaries.is_empty(), "text.entities.dictionaries must not be empty");
+        anyhow::ensure!(!self.text.entities.reload.is_zero(), "text.entities.reload must be > 0");
+        let day = Duration::from_secs(24 * 3600);
+        anyhow::ensure!(self.text.dedup.ttl >= day && self.text.dedup.ttl <= 3 * day, "text.dedup.ttl must be between 24h and 72h");
+        anyhow::ensure!(self.text.dedup.capacity > 0, "text.dedup.capacity must be > 0");
+        anyhow::ensure!(!self.text.dedup.stats_interval.is_zero(), "text.dedup.statsInterval must be > 0");
+        anyhow::ensure!(self.text.stories.max_distance < 64, "text.stories.maxDistance must be < 64");
+        let index = &self.strategy.index;
+        anyhow::ensure!(index.k1 >= 0.0, "strategy.index.k1 must be >= 0");
+        anyhow::ensure!((0.0..=1.0).contains(&index.b), "strategy.index.b must be within [0, 1]");
+        anyhow::ensure!(index.candidates > 0, "strategy.index.candidates must be > 0");
+        let boosts = &index.boosts;
+        anyhow::ensure!(
+            [boosts.title, boosts.description, boosts.tags, boosts.outcomes].iter().all(|w| *w >= 0.0),
+            "strategy.index.boosts must be >= 0"
+        );
+        let filters = &self.strategy.filters;
+        anyhow::ensure!(filters.liquidity.max_spread > 0.0, "strategy.filters.liquidity.maxSpread must be > 0");
+        for rule in &filters.category.rules {
+            anyhow::ensure!(!rule.tags.is_empty(), "strategy.filters.category rule {} has no tags", rule.name);
+        }
+        let norms = &self.strategy.scorer.norms;
+        anyhow::ensure!(norms.bm25_scale > 0.0, "strategy.scorer.norms.bm25Scale must be > 0");
+        anyhow::ensure!(norms.number_tolerance >= 0.0, "strategy.scorer.norms.numberTolerance must be >= 0");
+        anyhow::ensure!(!norms.time_decay.is_zero(), "strategy.scorer.norms.timeDecay must be > 0");
+        anyhow::ensure!((0.0..=1.0).contains(&norms.time_neutral), "strategy.scorer.norms.timeNeutral must be within [0, 1]");
+        anyhow::ensure!(norms.volume_scale > 0.0, "strategy.scorer.norms.volumeScale must be > 0");
+        anyhow::ensure!(norms.open_interest_scale > 0.0, "strategy.scorer.norms.openInterestScale must be > 0");
+        anyhow::ensure!(norms.spread_scale > 0.0, "strategy.scorer.norms.spreadScale must be > 0");
+        anyhow::ensure!(!norms.stale_after.is_zero(), "strategy.scorer.norms.staleAfter must be > 0");
+        let select = &self.strategy.select;
+        anyhow::ensure!((0.0..=1.0).contains(&select.lambda), "strategy.select.lambda must be within [0, 1]");
+        anyhow::ensure!(select.k > 0, "strategy.select.k must be > 0");
+        anyhow::ensure!((0.0..=1.0).contains(&select.same_event_similarity), "strategy.select.sameEventSimilarity must be within [0, 1]");
+        anyhow::ensure!(select.max_per_event > 0, "strategy.select.maxPerEvent must be > 0");
+        let trade = &self.strategy.trade;
+        anyhow::ensure!(trade.tau >= 0.0, "strategy.trade.tau must be >= 0");
+        anyhow::ensure!(trade.fee_buffer >= 0.0, "strategy.trade.feeBuffer must be >= 0");
+        anyhow::ensure!(!trade.quote_timeout.is_zero(), "strategy.trade.quoteTimeout must be > 0");
+        anyhow::ensure!(!trade.order_ttl.is_zero(), "strategy.trade.orderTtl must be > 0");
+        let sizing = &self.strategy.sizing;
+        anyhow::ensure!(sizing.kelly > 0.0 && sizing.kelly <= 1.0, "strategy.sizing.kelly must be within (0, 1]");
+        anyhow::ensure!(sizing.bankroll > 0.0, "strategy.sizing.bankroll must be > 0");
+        for (name, v) in [
+            ("maxMarketFraction", sizing.max_market_fraction),
+            ("maxEventFraction", sizing.max_event_fraction),
+            ("maxGrossFraction", sizing.max_gross_fraction),
+            ("maxDepthFraction", sizing.max_depth_fraction),
+        ] {
+            anyhow::ensure!(v > 0.0 && v <= 1.0, "strategy.sizing.{name} must be within (0, 1]");
+        }
+        let fees = &self.execution.fees;
+        anyhow::ensure!(fees.maker_bps >= 0.0 && fees.taker_bps >= 0.0, "execution.fees must be >= 0");
+        let policy = &self.execution.policy;
+        anyhow::ensure!(policy.cross_threshold >= 0.0, "execution.policy.crossThreshold must be >= 0");
+        anyhow::ensure!(policy.tick > 0.0 && policy.tick < 0.5, "execution.policy.tick must be within (0, 0.5)");
+        let slicing = &self.execution.slicing;
+        anyhow::ensure!(
+            slicing.max_depth_fraction > 0.0 && slicing.max_depth_fraction <= 1.0,
+            "execution.slicing.maxDepthFraction must be within (0, 1]"
+        );
+        anyhow::ensure!(slicing.min_child_size > 0.0, "execution.slicing.minChildSize must be > 0");
+        anyhow::ensure!(self.execution.paper.balance >= 0.0, "execution.paper.balance must be >= 0");
+        if matches!(self.execution.mode, ExecutionMode::Clob | ExecutionMode::Mock) {
+            let clob = self.execution.clob.as_ref().context("execution.clob required for modes clob and mock")?;
+            anyhow::ensure!(clob.signature_type <= 2, "execution.clob.signatureType must be 0, 1 or 2");
+            anyhow::ensure!(!clob.poll_interval.is_zero(), "execution.clob.pollInterval must be > 0");
+        }
+        let probability = &self.strategy.probability;
+        let calibrations = std::iter::once(("default", &probability.default))
+            .chain(probability.categories.iter().map(|(k, v)| (k.as_str(), v)));
+        for (name, c) in calibrations {
+            anyhow::ensure!((0.2..=0.6).contains(&c.lambda), "strategy.probability {name}: lambda must be within [0.2, 0.6]");
+            anyhow::ensure!(c.a.is_finite() && c.b.is_finite(), "strategy.probability {name}: a and b must be finite");
+        }
         Ok(())
     }
 }
\ No newline at end of file
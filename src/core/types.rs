@@ -1,5 +1,13 @@
 use anyhow::Result;
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use crate::enrichment::simhash::Story;
+use crate::text::dates::TimeWindow;
+use crate::text::entities::Entities;
+use crate::text::normalizer::NewsTokens;
+use crate::text::numbers::Quantity;
 
 #[async_trait::async_trait]
 pub trait Actor: Send + Sync + 'static {
@@ -17,6 +25,26 @@ pub struct RawNews {
     pub labels: Vec<String>
 }
 
+/// `RawNews` plus everything the text pipeline extracted from it.
+#[derive(Clone, Debug)]
+pub struct EnrichedNews {
+    pub raw: RawNews,
+    pub tokens: NewsTokens,
+    pub entities: Entities,
+    pub quantities: Vec<Quantity>,
+    pub time_windows: Vec<TimeWindow>,
+    pub content_hash: u64,
+    pub story: Story,
+    pub received_at: chrono::DateTime<chrono::Utc>,
+    /// received_at - published, when the feed gave a timestamp.
+    pub latency_ms: Option<i64>,
+}
+
+/// Asks the PolyActor to publish a `MarketChange::Snapshot`, e.g. after a consumer lagged
+/// and lost diffs.
+#[derive(Clone, Debug)]
+pub struct MarketSnapshotRequest;
+
 #[derive(Clone, Debug)]
 pub struct MarketDataRequest{
     pub market_id: String
@@ -32,25 +60,176 @@ pub struct MarketDataSnap {
     pub ask_size: f32,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum Outcome {
+    Yes,
+    No,
+}
+
+impl Outcome {
+    pub fn other(self) -> Self {
+        match self {
+            Outcome::Yes => Outcome::No,
+            Outcome::No => Outcome::Yes,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum OrderType {
+    /// Rests at `price` unless it crosses.
+    Limit,
+    /// Takes whatever is available up to `price`.
+    Market,
+}
+
+/// Polymarket CLOB time-in-force.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TimeInForce {
+    /// Good till cancelled.
+    Gtc,
+    /// Good till `Order::ttl` elapses.
+    Gtd,
+    /// Fill entirely at once or cancel.
+    Fok,
+    /// Fill what is available at once, cancel the rest.
+    Fak,
+}
+
+/// How hard execution should work to get filled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Urgency {
+    /// Never cross; join the touch.
+    Passive,
+    /// Quote inside the spread; cross only when the edge clears the crossing threshold.
+    Normal,
+    /// Cross whenever the edge survives spread and fees.
+    Urgent,
+}
+
+/// What the strategy wants from an order, in the order's outcome prices. Execution decides
+/// the actual limit price from this and the live book.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct OrderIntent {
+    /// Price the edge was measured against (the mid at decision time).
+    pub target_price: f32,
+    /// Worst acceptable price: the most a buy pays, the least a sell takes.
+    pub max_price: f32,
+    pub urgency: Urgency,
+    /// Expected value per contract over `target_price`.
+    pub edge: f64,
+}
+
+#[derive(Clone, Debug, Serialize)]
 pub struct Order {
     pub client_order_id: String,
     pub market_id: String,
+    pub side: Side,
+    pub outcome: Outcome,
+    /// CLOB token of `outcome`.
+    pub token_id: String,
+    pub order_type: OrderType,
+    pub time_in_force: TimeInForce,
+    /// Lifetime for `Gtd` orders.
+    pub ttl: Option<std::time::Duration>,
+    /// Reject instead of crossing the spread.
+    pub post_only: bool,
+    /// Market settles on the neg-risk exchange.
+    pub neg_risk: bool,
     pub price: f32,
     pub size: f32,
+    /// Set when execution may re-price the order; `price` is then the taking price the
+    /// strategy sized against.
+    pub intent: Option<OrderIntent>,
+}
+
+impl Order {
+    /// Time in force the venue sees: market orders never rest, so they go out FAK unless FOK.
+    pub fn effective_time_in_force(&self) -> TimeInForce {
+        if self.order_type == OrderType::Market && self.time_in_force != TimeInForce::Fok {
+            TimeInForce::Fak
+        } else {
+            self.time_in_force
+        }
+    }
+
+    /// Fills what it can on arrival and never rests.
+    pub fn is_immediate(&self) -> bool {
+        matches!(self.effective_time_in_force(), TimeInForce::Fok | TimeInForce::Fak)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderStatus {
+    New,
+    Acknowledged,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+    Expired,
+    Rejected,
+}
+
+impl OrderStatus {
+    /// No further fills or updates follow.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Expired | OrderStatus::Rejected)
+    }
+}
+
+/// Whether a fill added liquidity to the book or took it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Liquidity {
+    Maker,
+    Taker,
 }
 
 #[derive(Clone, Debug)]
 pub struct Execution {
     pub client_order_id: String,
+    /// Id the venue assigned to the order.
+    pub venue_order_id: String,
     pub market_id: String,
+    pub side: Side,
+    pub outcome: Outcome,
+    pub liquidity: Liquidity,
+    /// Order status after this fill.
+    pub status: OrderStatus,
     pub avg_px: f32,
     pub filled: f32,
     pub fee: f32,
     pub ts_ms: i64,
 }
 
+/// Order status change, published on `order_updates` by the order manager.
+#[derive(Clone, Debug)]
+pub struct OrderUpdate {
+    pub client_order_id: String,
+    pub venue_order_id: Option<String>,
+    pub market_id: String,
+    pub status: OrderStatus,
+    pub filled: f32,
+    pub remaining: f32,
+    pub avg_px: f32,
+    /// Why the order was rejected, cancelled or expired.
+    pub reason: Option<String>,
+    pub ts_ms: i64,
+}
+
+// ----------- Polymarket (Gamma API) -----------
+// Gamma is loose with types: numbers arrive as strings, arrays as stringified JSON, flags as
+// null. Everything below deserializes leniently so one odd row doesn't fail a whole page.
+
 #[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PolyMarketEvent {
     pub id: String,
     #[serde(default)]
@@ -58,10 +237,240 @@ pub struct PolyMarketEvent {
     #[serde(default)]
     pub description: Option<String>,
     #[serde(default)]
-    pub markets: Option<Vec<PolyMarketMarket>>
+    pub category: Option<String>,
+    #[serde(default, deserialize_with = "de_lenient_datetime")]
+    pub end_date: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "de_lenient_f64")]
+    pub open_interest: Option<f64>,
+    #[serde(default, deserialize_with = "de_null_default")]
+    pub tags: Vec<PolyTag>,
+    #[serde(default, deserialize_with = "de_null_default")]
+    pub markets: Vec<PolyMarketMarket>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PolyMarketMarket {
     pub id: String,
-}
\ No newline at end of file
+    #[serde(default)]
+    pub question: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Outcome label of this market inside a multi-market event ("50 bps decrease").
+    #[serde(default)]
+    pub group_item_title: Option<String>,
+    /// Outcome labels, index-aligned with `outcome_prices` and `clob_token_ids`.
+    #[serde(default, deserialize_with = "de_json_array")]
+    pub outcomes: Vec<String>,
+    #[serde(default, deserialize_with = "de_price_array")]
+    pub outcome_prices: Vec<f64>,
+    #[serde(default, deserialize_with = "de_json_array")]
+    pub clob_token_ids: Vec<String>,
+    #[serde(default, deserialize_with = "de_lenient_datetime")]
+    pub start_date: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "de_lenient_datetime")]
+    pub end_date: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "de_lenient_f64")]
+    pub liquidity: Option<f64>,
+    #[serde(default, deserialize_with = "de_lenient_f64")]
+    pub volume: Option<f64>,
+    #[serde(default, deserialize_with = "de_lenient_f64")]
+    pub volume_24hr: Option<f64>,
+    #[serde(default, deserialize_with = "de_lenient_f64")]
+    pub best_bid: Option<f64>,
+    #[serde(default, deserialize_with = "de_lenient_f64")]
+    pub best_ask: Option<f64>,
+    #[serde(default, deserialize_with = "de_lenient_f64")]
+    pub spread: Option<f64>,
+    #[serde(default, deserialize_with = "de_lenient_f64")]
+    pub last_trade_price: Option<f64>,
+    #[serde(default, deserialize_with = "de_lenient_f64")]
+    pub order_price_min_tick_size: Option<f64>,
+    #[serde(default, deserialize_with = "de_lenient_f64")]
+    pub order_min_size: Option<f64>,
+    #[serde(default, deserialize_with = "de_null_default")]
+    pub active: bool,
+    #[serde(default, deserialize_with = "de_null_default")]
+    pub closed: bool,
+    #[serde(default, deserialize_with = "de_null_default")]
+    pub archived: bool,
+    #[serde(default, deserialize_with = "de_null_default")]
+    pub accepting_orders: bool,
+    #[serde(default, deserialize_with = "de_null_default")]
+    pub neg_risk: bool,
+    #[serde(default, deserialize_with = "de_null_default")]
+    pub tags: Vec<PolyTag>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PolyTag {
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// A tradable market flattened out of its event, carrying the event context the matcher needs.
+#[derive(Clone, Debug)]
+pub struct IndexedMarket {
+    pub market: PolyMarketMarket,
+    pub event_id: String,
+    pub event_title: Option<String>,
+    /// Event and market tag labels, deduplicated.
+    pub tags: Vec<String>,
+    pub category: Option<String>,
+    pub open_interest: Option<f64>,
+    /// Market end date, falling back to the event's.
+    pub end_date: Option<DateTime<Utc>>,
+    /// Entities in the market text. Empty until tagged by the consumer's `EntityExtractor`.
+    pub entities: Entities,
+}
+
+impl IndexedMarket {
+    pub fn from_event(ev: &PolyMarketEvent) -> Vec<IndexedMarket> {
+        ev.markets
+            .iter()
+            .map(|m| {
+                let mut tags: Vec<String> = Vec::new();
+                for label in ev.tags.iter().chain(&m.tags).filter_map(|t| t.label.as_ref()) {
+                    if !tags.contains(label) {
+                        tags.push(label.clone());
+                    }
+                }
+                IndexedMarket {
+                    market: m.clone(),
+                    event_id: ev.id.clone(),
+                    event_title: ev.title.clone(),
+                    tags,
+                    category: m.category.clone().or_else(|| ev.category.clone()),
+                    open_interest: ev.open_interest,
+                    end_date: m.end_date.or(ev.end_date),
+                    entities: Entities::default(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn id(&self) -> &str {
+        &self.market.id
+    }
+
+    /// CLOB token for an outcome, matched by label and falling back to Yes/No position.
+    pub fn token_id(&self, outcome: Outcome) -> Option<&str> {
+        let (label, position) = match outcome {
+            Outcome::Yes => ("yes", 0),
+            Outcome::No => ("no", 1),
+        };
+        let m = &self.market;
+        let i = m.outcomes.iter().position(|o| o.eq_ignore_ascii_case(label)).unwrap_or(position);
+        m.clob_token_ids.get(i).map(String::as_str)
+    }
+
+    /// The market question, or the event title for markets without one.
+    pub fn title(&self) -> &str {
+        self.market.question.as_deref().or(self.event_title.as_deref()).unwrap_or_default()
+    }
+}
+
+/// Market attributes a catalogue refresh can report as changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarketField {
+    Question,
+    Description,
+    EventTitle,
+    Tags,
+    Category,
+    Outcomes,
+    EndDate,
+    Prices,
+    Quote,
+    Liquidity,
+    Volume,
+    OpenInterest,
+    AcceptingOrders,
+    TickSize,
+}
+
+impl MarketField {
+    /// Whether the market's BM25 document depends on this field.
+    pub fn is_indexed(self) -> bool {
+        matches!(
+            self,
+            MarketField::Question
+                | MarketField::Description
+                | MarketField::EventTitle
+                | MarketField::Tags
+                | MarketField::Category
+                | MarketField::Outcomes
+        )
+    }
+}
+
+/// Difference between two consecutive catalogue refreshes, per market.
+#[derive(Clone, Debug)]
+pub enum MarketChange {
+    Added(IndexedMarket),
+    Updated { market: IndexedMarket, changed: Vec<MarketField> },
+    /// No longer listed among active events.
+    Removed { market_id: String },
+    /// Still listed, but closed or deactivated.
+    Closed { market_id: String },
+    /// Every listed market, in answer to a `MarketSnapshotRequest`. Replaces what the
+    /// consumer built from earlier changes.
+    Snapshot(Vec<IndexedMarket>),
+}
+
+fn de_null_default<'de, D, T>(d: D) -> std::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(d)?.unwrap_or_default())
+}
+
+/// 0.55, "0.55", "" and null.
+fn de_lenient_f64<'de, D: Deserializer<'de>>(d: D) -> std::result::Result<Option<f64>, D::Error> {
+    Ok(value_to_f64(&Value::deserialize(d)?))
+}
+
+fn value_to_f64(v: &Value) -> Option<f64> {
+    match v {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// A JSON array, or the same array stringified (`"[\"Yes\", \"No\"]"`). Null and anything
+/// unparseable are empty.
+fn de_json_array<'de, D, T>(d: D) -> std::result::Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let v = match Value::deserialize(d)? {
+        Value::String(s) => serde_json::from_str(&s).unwrap_or(Value::Null),
+        v => v,
+    };
+    Ok(serde_json::from_value(v).unwrap_or_default())
+}
+
+/// Stringified array of stringified prices: `"[\"0.515\", \"0.485\"]"`. Empty if any price
+/// is unparseable, since prices are index-aligned with the outcomes.
+fn de_price_array<'de, D: Deserializer<'de>>(d: D) -> std::result::Result<Vec<f64>, D::Error> {
+    let raw: Vec<Value> = de_json_array(d)?;
+    Ok(raw.iter().map(value_to_f64).collect::<Option<_>>().unwrap_or_default())
+}
+
+/// RFC 3339 timestamps, bare dates ("2025-12-31", taken as midnight UTC), "" and null.
+fn de_lenient_datetime<'de, D: Deserializer<'de>>(d: D) -> std::result::Result<Option<DateTime<Utc>>, D::Error> {
+    let Some(s) = Option::<String>::deserialize(d)? else { return Ok(None) };
+    let s = s.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Ok(Some(dt.with_timezone(&Utc)));
+    }
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).map(|dt| dt.and_utc()));
+    }
+    Ok(None)
+}
@@ -0,0 +1,125 @@
+use crate::bus::types::Bus;
+use crate::config::config::TextCfg;
+use crate::core::types::{Actor, EnrichedNews, RawNews};
+use crate::enrichment::dedup::{content_hash, DedupCache};
+use crate::enrichment::simhash::{quantities_key, simhash, StoryTracker};
+use crate::text::dates::DateExtractor;
+use crate::text::entities::EntityExtractor;
+use crate::text::normalizer::Normalizer;
+use crate::text::numbers::extract_quantities;
+use anyhow::Result;
+use chrono::Utc;
+use tokio::time::{interval_at, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+/// Turns `raw_news` into `enriched_news`: tokens, entities, quantities, time windows, dedup.
+pub struct EnrichmentActor {
+    pub bus: Bus,
+    pub cfg: TextCfg,
+    pub normalizer: Normalizer,
+    pub entities: EntityExtractor,
+    pub dates: DateExtractor,
+    pub dedup: DedupCache,
+    pub stories: StoryTracker,
+    pub shutdown: CancellationToken
+}
+
+impl EnrichmentActor {
+    pub fn new(
+        bus: Bus,
+        cfg: TextCfg,
+        entities: EntityExtractor,
+        dates: DateExtractor,
+        shutdown: CancellationToken,
+    ) -> EnrichmentActor {
+        let dedup = DedupCache::new(cfg.dedup.capacity, cfg.dedup.ttl);
+        let stories = StoryTracker::new(cfg.stories.max_distance, cfg.stories.window);
+        Self { bus, cfg, normalizer: Normalizer::default(), entities, dates, dedup, stories, shutdown }
+    }
+
+    /// None when the item is a duplicate of one seen within the dedup TTL.
+    fn enrich(&mut self, news: &RawNews) -> Option<EnrichedNews> {
+        let received_at = Utc::now();
+        let tokens = self.normalizer.tokenize_news(news);
+        let content_hash = content_hash(&tokens.title, &news.url);
+        if !self.dedup.first_seen(content_hash, &news.feed) {
+            return None;
+        }
+
+        let quantities = extract_quantities(news);
+        let story = self.stories.assign(simhash(&tokens.title), quantities_key(&quantities), content_hash, &news.feed);
+        let entities = self.entities.extract(&tokens);
+        let time_windows = self.dates.extract(news, received_at);
+        let latency_ms = news.published.map(|p| (received_at - p).num_milliseconds());
+
+        Some(EnrichedNews {
+            raw: news.clone(),
+            tokens,
+            entities,
+            quantities,
+            time_windows,
+            content_hash,
+            story,
+            received_at,
+            latency_ms,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Actor for EnrichmentActor {
+    async fn run(mut self) -> Result<()> {
+        info!("EnrichmentActor started");
+        let mut rx = self.bus.raw_news.subscribe(); // broadcast::Receiver<Arc<RawNews>>
+
+        // dictionaries are re-read periodically so they can be edited without a restart
+        let reload = self.cfg.entities.reload;
+        let mut reload_tick = interval_at(Instant::now() + reload, reload);
+        let stats = self.cfg.dedup.stats_interval;
+        let mut stats_tick = interval_at(Instant::now() + stats, stats);
+
+        loop {
+            tokio::select! {
+                // Graceful shutdown signal
+                _ = self.shutdown.cancelled() => {
+                    info!("EnrichmentActor: shutdown requested");
+                    break;
+                }
+
+                _ = reload_tick.tick() => {
+                    match self.entities.reload() {
+                        Ok(true) => info!("EnrichmentActor: entity dictionaries reloaded"),
+                        Ok(false) => {}
+                        Err(e) => warn!(?e, "EnrichmentActor: entity dictionary reload failed, keeping previous"),
+                    }
+                }
+
+                _ = stats_tick.tick() => {
+                    info!(cached = self.dedup.len(), dropped = ?self.dedup.dropped(), "EnrichmentActor: dedup stats");
+                }
+
+                res = rx.recv() => {
+                    match res {
+                        Ok(news) => {
+                            match self.enrich(&news) {
+                                Some(enriched) => self.bus.enriched_news.publish(enriched).await?,
+                                None => debug!(feed = %news.feed, title = %news.title, "duplicate news dropped"),
+                            }
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                            warn!(lagged = n, "EnrichmentActor lagged on raw_news");
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                            error!("raw_news stream closed; exiting EnrichmentActor");
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        info!("EnrichmentActor stopped cleanly");
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
+use lru::LruCache;
+use xxhash_rust::xxh3::xxh3_64;
+use crate::text::normalizer::Token;
+
+/// Hash of the normalized title plus URL: survives case / punctuation / whitespace changes.
+pub fn content_hash(title: &[Token], url: &str) -> u64 {
+    let mut key = String::with_capacity(url.len() + 64);
+    for t in title {
+        key.push_str(&t.text);
+        key.push(' ');
+    }
+    key.push('|');
+    key.push_str(url.trim());
+    xxh3_64(key.as_bytes())
+}
+
+/// Exact-duplicate filter. Feeds are re-polled every few seconds and republish everything, so a
+/// hit refreshes the entry: an item is forgotten only after it has been absent for `ttl`.
+/// Capacity bounds memory; the least recently seen hashes go first.
+pub struct DedupCache {
+    ttl: Duration,
+    seen: LruCache<u64, Instant>,
+    dropped: HashMap<String, u64>,
+}
+
+impl DedupCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        let cap = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self { ttl, seen: LruCache::new(cap), dropped: HashMap::new() }
+    }
+
+    /// True the first time `hash` is seen within the TTL; otherwise counts a drop for `feed`.
+    pub fn first_seen(&mut self, hash: u64, feed: &str) -> bool {
+        let now = Instant::now();
+        let fresh = match self.seen.put(hash, now) {
+            Some(last) => now.duration_since(last) > self.ttl,
+            None => true,
+        };
+        if !fresh {
+            *self.dropped.entry(feed.to_string()).or_default() += 1;
+        }
+        fresh
+    }
+
+    /// Duplicates dropped per feed since startup.
+    pub fn dropped(&self) -> &HashMap<String, u64> {
+        &self.dropped
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod actor;
+pub mod dedup;
+pub mod simhash;
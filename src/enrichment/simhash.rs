@@ -0,0 +1,95 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use xxhash_rust::xxh3::xxh3_64;
+use crate::text::normalizer::{TextField, Token};
+use crate::text::numbers::Quantity;
+
+// Numbers carry the news ("cuts 25bps" vs "cuts 50bps"), so they outvote plain words.
+const NUMBER_WEIGHT: i32 = 3;
+
+/// 64-bit SimHash over normalized tokens; paraphrases land a few bits apart.
+pub fn simhash<'a>(tokens: impl IntoIterator<Item = &'a Token>) -> u64 {
+    let mut votes = [0i32; 64];
+    for t in tokens {
+        let h = xxh3_64(t.text.as_bytes());
+        let w = if t.text.starts_with(|c: char| c.is_ascii_digit()) { NUMBER_WEIGHT } else { 1 };
+        for (bit, v) in votes.iter_mut().enumerate() {
+            *v += if h >> bit & 1 == 1 { w } else { -w };
+        }
+    }
+    votes.iter().enumerate().fold(0u64, |acc, (bit, v)| if *v > 0 { acc | 1 << bit } else { acc })
+}
+
+/// Hash of the headline's quantities, unit-normalized and order-free. Headlines that differ
+/// only in a number ("cuts 25bps" vs "cuts 50bps") are close in SimHash but different news.
+pub fn quantities_key(quantities: &[Quantity]) -> u64 {
+    let mut parts: Vec<String> = quantities
+        .iter()
+        .filter(|q| q.field == TextField::Title)
+        .map(|q| match q.as_percent() {
+            Some(p) => format!("{p}%"),
+            None => format!("{:?}", q.kind),
+        })
+        .collect();
+    parts.sort();
+    parts.dedup();
+    xxh3_64(parts.join("|").as_bytes())
+}
+
+/// Group of near-identical headlines across feeds. The first arrival is the signal, later
+/// ones are confirmations of it.
+#[derive(Clone, Debug)]
+pub struct Story {
+    /// Content hash of the first arrival.
+    pub id: u64,
+    pub first: bool,
+    /// Feeds that carried the story so far, in arrival order.
+    pub feeds: Vec<String>,
+}
+
+/// Assigns each fingerprint to the story of the closest recent arrival within `max_distance`
+/// bits and with the same quantities, or opens a new one. Every arrival keeps its own fingerprint so a story whose wording
+/// drifts still matches; arrivals and stories older than `window` are forgotten.
+pub struct StoryTracker {
+    max_distance: u32,
+    window: Duration,
+    /// (arrival, fingerprint, quantities key, story)
+    recent: VecDeque<(Instant, u64, u64, u64)>,
+    stories: HashMap<u64, (Instant, Vec<String>)>,
+}
+
+impl StoryTracker {
+    pub fn new(max_distance: u32, window: Duration) -> Self {
+        Self { max_distance, window, recent: VecDeque::new(), stories: HashMap::new() }
+    }
+
+    pub fn assign(&mut self, fingerprint: u64, quantities: u64, content_hash: u64, feed: &str) -> Story {
+        let now = Instant::now();
+        while self.recent.front().is_some_and(|(seen, ..)| now.duration_since(*seen) > self.window) {
+            self.recent.pop_front();
+        }
+        self.stories.retain(|_, (seen, _)| now.duration_since(*seen) <= self.window);
+
+        let closest = self
+            .recent
+            .iter()
+            .filter(|(_, _, key, _)| *key == quantities)
+            .map(|(_, fp, _, story)| ((fp ^ fingerprint).count_ones(), *story))
+            .filter(|(d, _)| *d <= self.max_distance)
+            .min_by_key(|(d, _)| *d)
+            .map(|(_, story)| story);
+
+        let (id, first) = match closest {
+            Some(id) if self.stories.contains_key(&id) => (id, false),
+            _ => (content_hash, true),
+        };
+        let (seen, feeds) = self.stories.entry(id).or_insert_with(|| (now, Vec::new()));
+        *seen = now;
+        if !feeds.iter().any(|f| f == feed) {
+            feeds.push(feed.to_string());
+        }
+        self.recent.push_back((now, fingerprint, quantities, id));
+
+        Story { id, first, feeds: feeds.clone() }
+    }
+}
@@ -1,18 +1,233 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use anyhow::Result;
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info};
+use tracing::{debug, error, info, warn};
 use crate::bus::types::Bus;
-use crate::core::types::{Actor};
-use crate::core::types::Execution;
+use crate::core::types::{Actor, MarketDataSnap, Order, OrderStatus, OrderUpdate};
+use crate::execution::orders::{validate, OrderManager};
+use crate::execution::slicer::Slicer;
+use crate::execution::types::VenueEvent;
+use crate::execution::venue::Venue;
+
+/// How often open orders are checked against their ttl.
+const EXPIRY_INTERVAL: Duration = Duration::from_millis(500);
 
 pub struct ExecutionActor {
     pub bus: Bus,
+    pub orders: OrderManager,
+    pub venue: Box<dyn Venue>,
+    /// Works each order through child orders at the venue.
+    pub slicer: Slicer,
+    /// Latest snapshot per market.
+    pub books: HashMap<String, MarketDataSnap>,
     pub shutdown: CancellationToken
 }
 
 impl ExecutionActor {
-    pub fn new(bus: Bus, shutdown: CancellationToken) -> ExecutionActor {
-        Self { bus, shutdown }
+    pub fn new(bus: Bus, venue: Box<dyn Venue>, slicer: Slicer, shutdown: CancellationToken) -> ExecutionActor {
+        Self { bus, orders: OrderManager::default(), venue, slicer, books: HashMap::new(), shutdown }
+    }
+
+    /// Orders are tracked, published and expired as the strategy sent them; the venue only
+    /// sees their children.
+    async fn on_order(&mut self, order: &Order) -> Result<()> {
+        let Some(update) = self.orders.submit(order.clone()) else {
+            warn!(client_order_id = %order.client_order_id, "duplicate order ignored");
+            return Ok(());
+        };
+        self.publish(update).await?;
+        if let Err(reason) = validate(order) {
+            let update = self.orders.reject(&order.client_order_id, &reason)?;
+            return self.publish(update).await;
+        }
+        self.slicer.add(order.clone());
+        self.work(Some(&order.market_id)).await
+    }
+
+    async fn on_market_data(&mut self, snap: &MarketDataSnap) -> Result<()> {
+        self.books.insert(snap.market_id.clone(), snap.clone());
+        let events = self.venue.on_book(snap).await;
+        self.apply(events).await?;
+        self.requote(snap).await?;
+        self.work(Some(&snap.market_id)).await
+    }
+
+    /// Sends the next child of every order with nothing working, in `market_id` if given.
+    async fn work(&mut self, market_id: Option<&str>) -> Result<()> {
+        for (parent_id, market_id) in self.slicer.parents(market_id) {
+            let Some(child) = self.slicer.next(&parent_id, self.books.get(&market_id), Instant::now()) else { continue };
+            let events = self.venue.place(&child).await;
+            self.apply(events).await?;
+        }
+        Ok(())
+    }
+
+    /// Re-prices resting children whose price the new book has moved. Neither venue amends in
+    /// place, so the old child is cancelled first and its replacement sized after whatever it
+    /// filled on the way out; if the cancel fails the old child keeps working.
+    async fn requote(&mut self, snap: &MarketDataSnap) -> Result<()> {
+        for (parent_id, _) in self.slicer.parents(Some(&snap.market_id)) {
+            let Some(old) = self.slicer.requote(&parent_id, snap, Instant::now()) else { continue };
+            match self.venue.cancel(&old).await {
+                Ok(fills) => self.apply(fills).await?,
+                Err(e) => {
+                    warn!(child = %old, error = %format!("{e:#}"), "requote cancel failed; child left working");
+                    continue;
+                }
+            }
+            debug!(parent = %parent_id, child = %old, "child cancelled for requote");
+            let Some(child) = self.slicer.replace(&parent_id, &old, snap, Instant::now()) else { continue };
+            let events = self.venue.place(&child).await;
+            self.apply(events).await?;
+        }
+        Ok(())
+    }
+
+    async fn poll_venue(&mut self) -> Result<()> {
+        let events = self.venue.poll().await;
+        self.apply(events).await
+    }
+
+    /// Logs what the venue holds and has working; a live venue that can't answer likely has bad credentials or url.
+    async fn log_holdings(&self) -> Result<()> {
+        let balances = self.venue.balances().await?;
+        info!(venue = self.venue.name(), cash = balances.cash, reserved = balances.reserved, "venue balances");
+        for p in self.venue.positions().await? {
+            info!(venue = self.venue.name(), token_id = %p.token_id, market_id = %p.market_id, outcome = ?p.outcome, size = p.size, "venue position");
+        }
+        for o in self.venue.open_orders() {
+            info!(
+                venue = self.venue.name(),
+                client_order_id = %o.client_order_id,
+                venue_order_id = %o.venue_order_id,
+                market_id = %o.market_id,
+                side = ?o.side,
+                outcome = ?o.outcome,
+                price = o.price,
+                remaining = o.remaining,
+                "venue open order"
+            );
+        }
+        Ok(())
+    }
+
+    /// Feeds venue reports on children through the order manager as reports on their parents
+    /// and publishes the results. A child that stops working without filling leaves its size
+    /// to the parent's next child; only the first child's rejection, or the end of an
+    /// immediate order, is final for the parent. A report the state machine refuses is logged
+    /// and skipped.
+    async fn apply(&mut self, events: Vec<VenueEvent>) -> Result<()> {
+        for event in events {
+            let child_id = match &event {
+                VenueEvent::Acknowledged { client_order_id, .. }
+                | VenueEvent::Fill { client_order_id, .. }
+                | VenueEvent::Cancelled { client_order_id, .. }
+                | VenueEvent::Rejected { client_order_id, .. } => client_order_id.clone(),
+            };
+            let Some(parent_id) = self.slicer.parent(&child_id).map(str::to_string) else {
+                debug!(child = %child_id, "report for a finished order ignored");
+                continue;
+            };
+            let status = self.orders.get(&parent_id).map(|s| s.status);
+            let applied = match event {
+                VenueEvent::Acknowledged { venue_order_id, .. } => {
+                    if status != Some(OrderStatus::New) {
+                        continue;
+                    }
+                    self.orders.acknowledge(&parent_id, venue_order_id).map(|u| (u, None))
+                }
+                VenueEvent::Fill { price, size, fee, liquidity, .. } => {
+                    // only a fill the order manager accepts counts toward the slices
+                    let applied = self.orders.fill(&parent_id, price, size, fee, liquidity);
+                    if applied.is_ok() {
+                        self.slicer.fill(&child_id, size);
+                    }
+                    applied.map(|(e, u)| (u, Some(e)))
+                }
+                VenueEvent::Cancelled { reason, .. } => {
+                    if !self.slicer.finish(&child_id) || !self.slicer.immediate(&parent_id) {
+                        debug!(parent = %parent_id, child = %child_id, reason = %reason, "child cancelled");
+                        continue;
+                    }
+                    self.orders.cancel(&parent_id, &reason).map(|u| (u, None))
+                }
+                VenueEvent::Rejected { reason, .. } => {
+                    self.slicer.finish(&child_id);
+                    if status != Some(OrderStatus::New) {
+                        warn!(parent = %parent_id, child = %child_id, reason = %reason, "child rejected; size re-queued");
+                        continue;
+                    }
+                    self.orders.reject(&parent_id, &reason).map(|u| (u, None))
+                }
+            };
+            match applied {
+                Ok((update, execution)) => {
+                    if update.status.is_terminal() {
+                        self.slicer.remove(&parent_id);
+                    }
+                    self.publish(update).await?;
+                    if let Some(execution) = execution {
+                        info!(
+                            client_order_id = %execution.client_order_id,
+                            venue_order_id = %execution.venue_order_id,
+                            market_id = %execution.market_id,
+                            side = ?execution.side,
+                            outcome = ?execution.outcome,
+                            liquidity = ?execution.liquidity,
+                            status = ?execution.status,
+                            avg_px = execution.avg_px,
+                            filled = execution.filled,
+                            fee = execution.fee,
+                            ts_ms = execution.ts_ms,
+                            "execution"
+                        );
+                        self.bus.executions.publish(execution).await?;
+                    }
+                }
+                Err(e) => warn!(error = %e, "venue report ignored"),
+            }
+        }
+        Ok(())
+    }
+
+    async fn expire_orders(&mut self) -> Result<()> {
+        for id in self.orders.expired() {
+            if let Some(child) = self.slicer.active(&id).map(str::to_string) {
+                match self.venue.cancel(&child).await {
+                    // fills that beat the cancel still count
+                    Ok(fills) => self.apply(fills).await?,
+                    Err(e) => warn!(client_order_id = %child, error = %format!("{e:#}"), "venue cancel failed"),
+                }
+            }
+            self.slicer.remove(&id);
+            // a late fill may have completed it
+            if !self.orders.get(&id).is_some_and(|s| s.is_open()) {
+                continue;
+            }
+            let update = self.orders.expire(&id)?;
+            self.publish(update).await?;
+        }
+        Ok(())
+    }
+
+    /// Publishes a status change and refreshes the open-orders view.
+    async fn publish(&self, update: OrderUpdate) -> Result<()> {
+        info!(
+            client_order_id = %update.client_order_id,
+            venue_order_id = ?update.venue_order_id,
+            market_id = %update.market_id,
+            status = ?update.status,
+            filled = update.filled,
+            remaining = update.remaining,
+            avg_px = update.avg_px,
+            reason = ?update.reason,
+            ts_ms = update.ts_ms,
+            "order update"
+        );
+        self.bus.order_updates.publish(update).await?;
+        self.bus.open_orders.send_replace(self.orders.open());
+        Ok(())
     }
 }
 
@@ -20,37 +235,56 @@ impl ExecutionActor {
 impl Actor for ExecutionActor {
     async fn run(mut self) -> Result<()> {
 
-        info!("ExecutionActor started");
-        let mut rx = self.bus.orders.subscribe(); // broadcast::Receiver<Arc<MarketDataRequest>>
+        info!(venue = self.venue.name(), "ExecutionActor started");
+        if let Err(e) = self.log_holdings().await {
+            warn!(venue = self.venue.name(), error = %format!("{e:#}"), "venue holdings unavailable");
+        }
+        let poll_interval = self.venue.poll_interval();
+        let mut rx = self.bus.orders.subscribe(); // broadcast::Receiver<Arc<Order>>
+        let mut md_rx = self.bus.market_data.subscribe(); // broadcast::Receiver<Arc<MarketDataSnap>>
+        let mut expiry = tokio::time::interval(EXPIRY_INTERVAL);
+        let mut poll = tokio::time::interval(poll_interval.unwrap_or(EXPIRY_INTERVAL));
         loop {
             tokio::select! {
+                // in order, so the book an order was decided on is seen before the order
+                biased;
+
                 // Graceful shutdown signal
                 _ = self.shutdown.cancelled() => {
                         info!("ExecutionActor: shutdown requested");
                         break;
                 }
 
+                // Order ttl, and remainders waiting for their next child
+                _ = expiry.tick() => {
+                    self.expire_orders().await?;
+                    self.work(None).await?;
+                }
+
+                // Fills from a live venue
+                _ = poll.tick(), if poll_interval.is_some() => self.poll_venue().await?,
+
+                // Books for the venue
+                res = md_rx.recv() => {
+                    match res {
+                        Ok(snap) => self.on_market_data(&snap).await?,
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                            warn!(lagged = n, "ExecutionActor lagged on market_data");
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                            error!("market_data stream closed; exiting ExecutionActor");
+                            break;
+                        }
+                    }
+                }
+
                 // Order requests
                 res = rx.recv() => {
                     match res {
-                        Ok(req) => {
-                            // TODO: send order
-
-                            // TODO:: GET FILL
-                            let fill = Execution {
-                                client_order_id: "".to_string(),
-                                market_id: "".to_string(),
-                                avg_px: 0.0,
-                                filled: 0.0,
-                                fee: 0.0,
-                                ts_ms: 0,
-                            };
-
-                            self.bus.executions.publish(fill).await?;
-                        }
+                        Ok(req) => self.on_order(&req).await?,
                         Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
                             // a slow consumer skipped n messages
-                            error!("ExecutionActor lagged by {n} MarketDataRequest messages");
+                            error!("ExecutionActor lagged by {n} Order messages");
                             continue;
                         }
                         Err(tokio::sync::broadcast::error::RecvError::Closed) => {
@@ -66,4 +300,4 @@ impl Actor for ExecutionActor {
         info!("ExecutionActor stopped cleanly");
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,528 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE;
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Method};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tracing::{debug, warn};
+use crate::config::config::{ClobCfg, FeesCfg};
+use crate::core::types::{Liquidity, Order, Outcome, Side, TimeInForce};
+use crate::execution::eip712::{self, Address, ClobOrder, Wallet, SIDE_BUY, SIDE_SELL};
+use crate::execution::types::{Balances, Position, VenueEvent, VenueOrder};
+use crate::execution::venue::{reserved, Venue};
+
+/// The exchange rejects GTD orders expiring sooner than this.
+const GTD_EXPIRY_MARGIN_SECS: u64 = 60;
+/// Amounts on the wire are in 1e-6 units.
+const UNITS: f64 = 1e6;
+
+// ----------- wire types -----------
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderPayload {
+    pub salt: u64,
+    pub maker: String,
+    pub signer: String,
+    pub taker: String,
+    pub token_id: String,
+    pub maker_amount: String,
+    pub taker_amount: String,
+    pub expiration: String,
+    pub nonce: String,
+    pub fee_rate_bps: String,
+    /// "BUY" or "SELL".
+    pub side: String,
+    pub signature_type: u8,
+    pub signature: String,
+}
+
+impl OrderPayload {
+    pub fn new(o: &ClobOrder, signature: String) -> Self {
+        Self {
+            salt: o.salt,
+            maker: eip712::format_address(&o.maker),
+            signer: eip712::format_address(&o.signer),
+            taker: eip712::format_address(&o.taker),
+            token_id: o.token_id.clone(),
+            maker_amount: o.maker_amount.to_string(),
+            taker_amount: o.taker_amount.to_string(),
+            expiration: o.expiration.to_string(),
+            nonce: o.nonce.to_string(),
+            fee_rate_bps: o.fee_rate_bps.to_string(),
+            side: if o.side == SIDE_BUY { "BUY" } else { "SELL" }.to_string(),
+            signature_type: o.signature_type,
+            signature,
+        }
+    }
+
+    /// The signed struct back out of its JSON form.
+    pub fn to_order(&self) -> Result<ClobOrder> {
+        Ok(ClobOrder {
+            salt: self.salt,
+            maker: eip712::parse_address(&self.maker)?,
+            signer: eip712::parse_address(&self.signer)?,
+            taker: eip712::parse_address(&self.taker)?,
+            token_id: self.token_id.clone(),
+            maker_amount: self.maker_amount.parse().context("makerAmount")?,
+            taker_amount: self.taker_amount.parse().context("takerAmount")?,
+            expiration: self.expiration.parse().context("expiration")?,
+            nonce: self.nonce.parse().context("nonce")?,
+            fee_rate_bps: self.fee_rate_bps.parse().context("feeRateBps")?,
+            side: match self.side.as_str() {
+                "BUY" => SIDE_BUY,
+                "SELL" => SIDE_SELL,
+                other => bail!("unknown side {other}"),
+            },
+            signature_type: self.signature_type,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostOrderRequest {
+    pub order: OrderPayload,
+    /// API key of the order owner.
+    pub owner: String,
+    /// GTC, GTD, FOK or FAK.
+    pub order_type: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub post_only: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostOrderResponse {
+    #[serde(default)]
+    pub success: bool,
+    #[serde(default)]
+    pub error_msg: String,
+    #[serde(rename = "orderID", default)]
+    pub order_id: String,
+    /// "matched", "live", "delayed" or "unmatched".
+    #[serde(default)]
+    pub status: String,
+    /// What the maker gave and received when matched on arrival, in whole units.
+    #[serde(default)]
+    pub making_amount: String,
+    #[serde(default)]
+    pub taking_amount: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CancelRequest {
+    #[serde(rename = "orderID")]
+    pub order_id: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CancelResponse {
+    #[serde(default)]
+    pub canceled: Vec<String>,
+    #[serde(default)]
+    pub not_canceled: HashMap<String, String>,
+}
+
+/// `GET /data/order/{id}`; sizes and price in whole units.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OpenOrder {
+    pub id: String,
+    /// "LIVE", "MATCHED" or "CANCELED".
+    pub status: String,
+    pub original_size: String,
+    pub size_matched: String,
+    pub price: String,
+    pub side: String,
+    pub asset_id: String,
+}
+
+/// `GET /balance-allowance`; amounts in 1e-6 units.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BalanceAllowance {
+    pub balance: String,
+    #[serde(default)]
+    pub allowance: String,
+}
+
+// ----------- auth -----------
+
+#[derive(Clone, Debug)]
+pub struct ApiCreds {
+    pub key: String,
+    /// URL-safe base64.
+    pub secret: String,
+    pub passphrase: String,
+}
+
+impl ApiCreds {
+    pub fn resolve(cfg: &ClobCfg) -> Result<Self> {
+        Ok(Self {
+            key: secret(&cfg.api_key, "POLY_API_KEY")?,
+            secret: secret(&cfg.api_secret, "POLY_API_SECRET")?,
+            passphrase: secret(&cfg.api_passphrase, "POLY_PASSPHRASE")?,
+        })
+    }
+}
+
+/// Config value, or the environment variable when it is empty.
+pub fn secret(value: &str, env: &str) -> Result<String> {
+    if !value.is_empty() {
+        return Ok(value.to_string());
+    }
+    std::env::var(env).with_context(|| format!("{env} not set and no value in execution.clob"))
+}
+
+/// L2 header signature: URL-safe base64 HMAC-SHA256 of `timestamp ‖ method ‖ path ‖ body`.
+pub fn l2_signature(secret: &str, timestamp: u64, method: &str, path: &str, body: &str) -> Result<String> {
+    let key = URL_SAFE.decode(secret).context("api secret is not base64")?;
+    let mut mac = Hmac::<Sha256>::new_from_slice(&key).context("hmac key")?;
+    mac.update(format!("{timestamp}{method}{path}{body}").as_bytes());
+    Ok(URL_SAFE.encode(mac.finalize().into_bytes()))
+}
+
+// ----------- client -----------
+
+/// Signs and submits orders with L2 (API key) authentication.
+pub struct ClobClient {
+    http: Client,
+    url: String,
+    creds: ApiCreds,
+    wallet: Wallet,
+    maker: Address,
+    signature_type: u8,
+    chain_id: u64,
+    exchange: Address,
+    neg_risk_exchange: Address,
+    fee_rate_bps: u64,
+}
+
+impl ClobClient {
+    pub fn new(http: Client, cfg: &ClobCfg, fees: &FeesCfg) -> Result<Self> {
+        let wallet = Wallet::from_hex(&secret(&cfg.private_key, "POLY_PRIVATE_KEY")?)?;
+        // proxy and safe orders are made by the funding wallet, never by the signer itself
+        if cfg.signature_type != 0 && cfg.funder.is_empty() {
+            bail!("execution.clob.funder is required with signatureType {}", cfg.signature_type);
+        }
+        let maker = if cfg.funder.is_empty() { wallet.address } else { eip712::parse_address(&cfg.funder)? };
+        Ok(Self {
+            http,
+            url: cfg.url.trim_end_matches('/').to_string(),
+            creds: ApiCreds::resolve(cfg)?,
+            maker,
+            signature_type: cfg.signature_type,
+            chain_id: cfg.chain_id,
+            exchange: eip712::parse_address(&cfg.exchange)?,
+            neg_risk_exchange: eip712::parse_address(&cfg.neg_risk_exchange)?,
+            fee_rate_bps: fees.taker_bps.round() as u64,
+            wallet,
+        })
+    }
+
+    /// The signed request for an order.
+    pub fn build(&self, order: &Order) -> Result<PostOrderRequest> {
+        let (maker_amount, taker_amount) = amounts(order.side, order.price as f64, order.size as f64)?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let time_in_force = order.effective_time_in_force();
+        let expiration = match (time_in_force, order.ttl) {
+            (TimeInForce::Gtd, Some(ttl)) => now.as_secs() + GTD_EXPIRY_MARGIN_SECS + ttl.as_secs(),
+            _ => 0,
+        };
+        let clob_order = ClobOrder {
+            // unique per order; kept below 2^53 since clients read it as a JSON number
+            salt: (now.as_nanos() % (1u128 << 53)) as u64,
+            maker: self.maker,
+            signer: self.wallet.address,
+            taker: [0; 20],
+            token_id: order.token_id.clone(),
+            maker_amount,
+            taker_amount,
+            expiration,
+            nonce: 0,
+            fee_rate_bps: self.fee_rate_bps,
+            side: if order.side == Side::Buy { SIDE_BUY } else { SIDE_SELL },
+            signature_type: self.signature_type,
+        };
+        let exchange = if order.neg_risk { &self.neg_risk_exchange } else { &self.exchange };
+        let signature = self.wallet.sign(&clob_order.digest(self.chain_id, exchange)?)?;
+        let order_type = match time_in_force {
+            TimeInForce::Gtc => "GTC",
+            TimeInForce::Gtd => "GTD",
+            TimeInForce::Fok => "FOK",
+            TimeInForce::Fak => "FAK",
+        };
+        Ok(PostOrderRequest {
+            order: OrderPayload::new(&clob_order, signature),
+            owner: self.creds.key.clone(),
+            order_type: order_type.to_string(),
+            post_only: order.post_only,
+        })
+    }
+
+    pub async fn post_order(&self, req: &PostOrderRequest) -> Result<PostOrderResponse> {
+        self.request(Method::POST, "/order", Some(serde_json::to_string(req)?)).await
+    }
+
+    pub async fn cancel(&self, order_id: &str) -> Result<CancelResponse> {
+        let body = serde_json::to_string(&CancelRequest { order_id: order_id.to_string() })?;
+        self.request(Method::DELETE, "/order", Some(body)).await
+    }
+
+    pub async fn order(&self, order_id: &str) -> Result<OpenOrder> {
+        self.request(Method::GET, &format!("/data/order/{order_id}"), None).await
+    }
+
+    /// USDC when `token_id` is `None`, else that outcome token.
+    pub async fn balance(&self, token_id: Option<&str>) -> Result<BalanceAllowance> {
+        let query = match token_id {
+            Some(token_id) => format!("asset_type=CONDITIONAL&token_id={token_id}"),
+            None => "asset_type=COLLATERAL".to_string(),
+        };
+        let path = format!("/balance-allowance?{query}&signature_type={}", self.signature_type);
+        self.request(Method::GET, &path, None).await
+    }
+
+    async fn request<T: DeserializeOwned>(&self, method: Method, path: &str, body: Option<String>) -> Result<T> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let body = body.unwrap_or_default();
+        // the query string is not signed
+        let signed_path = path.split('?').next().unwrap_or(path);
+        let signature = l2_signature(&self.creds.secret, timestamp, method.as_str(), signed_path, &body)?;
+        let mut req = self
+            .http
+            .request(method.clone(), format!("{}{path}", self.url))
+            .header("POLY_ADDRESS", eip712::format_address(&self.wallet.address))
+            .header("POLY_SIGNATURE", signature)
+            .header("POLY_TIMESTAMP", timestamp.to_string())
+            .header("POLY_API_KEY", &self.creds.key)
+            .header("POLY_PASSPHRASE", &self.creds.passphrase);
+        if !body.is_empty() {
+            req = req.header("Content-Type", "application/json").body(body);
+        }
+        let resp = req.send().await.with_context(|| format!("{method} {path}"))?;
+        let status = resp.status();
+        let text = resp.text().await.with_context(|| format!("{method} {path}: reading body"))?;
+        if !status.is_success() {
+            bail!("{method} {path}: {status}: {text}");
+        }
+        serde_json::from_str(&text).with_context(|| format!("{method} {path}: decoding {text}"))
+    }
+}
+
+/// (makerAmount, takerAmount) in 1e-6 units: a buy gives USDC for tokens, a sell tokens for
+/// USDC. Size is cut to 0.01 contracts and price to 0.0001 so the USDC side is exact.
+fn amounts(side: Side, price: f64, size: f64) -> Result<(u128, u128)> {
+    let size_c = (size * 100.0).floor();
+    let price_t = (price * 10_000.0).round();
+    if size_c < 1.0 {
+        bail!("size {size} below 0.01");
+    }
+    if !(1.0..10_000.0).contains(&price_t) {
+        bail!("price {price} outside (0, 1)");
+    }
+    let tokens = size_c as u128 * 10_000;
+    let usdc = size_c as u128 * price_t as u128;
+    Ok(match side {
+        Side::Buy => (usdc, tokens),
+        Side::Sell => (tokens, usdc),
+    })
+}
+
+// ----------- venue -----------
+
+#[derive(Clone, Debug)]
+struct Live {
+    order: Order,
+    venue_order_id: String,
+    /// Size matched so far, as last reported.
+    matched: f64,
+}
+
+/// Live venue: places signed orders and polls the ones left resting for fills.
+pub struct ClobVenue {
+    client: ClobClient,
+    fees: FeesCfg,
+    poll_interval: Duration,
+    live: HashMap<String, Live>,
+    /// Tokens ordered this session, by token id, for position queries.
+    tokens: HashMap<String, (String, Outcome)>,
+}
+
+impl ClobVenue {
+    pub fn new(http: Client, cfg: &ClobCfg, fees: &FeesCfg) -> Result<Self> {
+        Ok(Self {
+            client: ClobClient::new(http, cfg, fees)?,
+            fees: fees.clone(),
+            poll_interval: cfg.poll_interval,
+            live: HashMap::new(),
+            tokens: HashMap::new(),
+        })
+    }
+
+    fn fill(&self, client_order_id: &str, price: f64, size: f64, liquidity: Liquidity) -> VenueEvent {
+        let bps = match liquidity {
+            Liquidity::Maker => self.fees.maker_bps,
+            Liquidity::Taker => self.fees.taker_bps,
+        };
+        let fee = bps / 10_000.0 * price.min(1.0 - price) * size;
+        VenueEvent::Fill { client_order_id: client_order_id.to_string(), price, size, fee, liquidity }
+    }
+}
+
+#[async_trait::async_trait]
+impl Venue for ClobVenue {
+    fn name(&self) -> &'static str {
+        "clob"
+    }
+
+    async fn place(&mut self, order: &Order) -> Vec<VenueEvent> {
+        let id = order.client_order_id.clone();
+        let rejected = |reason: String| vec![VenueEvent::Rejected { client_order_id: id.clone(), reason }];
+        let req = match self.client.build(order) {
+            Ok(req) => req,
+            Err(e) => return rejected(format!("{e:#}")),
+        };
+        let resp = match self.client.post_order(&req).await {
+            Ok(resp) => resp,
+            Err(e) => return rejected(format!("{e:#}")),
+        };
+        if !resp.success || resp.order_id.is_empty() {
+            return rejected(resp.error_msg);
+        }
+        debug!(client_order_id = %id, venue_order_id = %resp.order_id, status = %resp.status, "clob order placed");
+        self.tokens.insert(order.token_id.clone(), (order.market_id.clone(), order.outcome));
+
+        let mut events = vec![VenueEvent::Acknowledged { client_order_id: id.clone(), venue_order_id: resp.order_id.clone() }];
+        let mut matched = 0.0;
+        if resp.status == "matched" {
+            let (making, taking) = (parse_amount(&resp.making_amount), parse_amount(&resp.taking_amount));
+            let (tokens, usdc) = if order.side == Side::Buy { (taking, making) } else { (making, taking) };
+            if tokens > 0.0 {
+                matched = tokens;
+                events.push(self.fill(&id, usdc / tokens, tokens, Liquidity::Taker));
+            }
+        }
+        let resting = !order.is_immediate();
+        if resting && matched < order.size as f64 - 1e-6 {
+            self.live.insert(id, Live { order: order.clone(), venue_order_id: resp.order_id, matched });
+        } else if !resting && matched < order.size as f64 - 1e-6 {
+            events.push(VenueEvent::Cancelled { client_order_id: id, reason: "unfilled remainder".to_string() });
+        }
+        events
+    }
+
+    /// Cancels at the venue and reads the order once more, so fills that raced the cancel
+    /// are reported. The order stays live, and keeps being polled, until the venue confirms.
+    async fn cancel(&mut self, client_order_id: &str) -> Result<Vec<VenueEvent>> {
+        let Some(live) = self.live.get(client_order_id) else { bail!("order {client_order_id} is not live") };
+        let venue_order_id = live.venue_order_id.clone();
+        let resp = self.client.cancel(&venue_order_id).await?;
+        if let Some(reason) = resp.not_canceled.get(&venue_order_id) {
+            bail!("order {client_order_id} not cancelled: {reason}");
+        }
+        if !resp.canceled.contains(&venue_order_id) {
+            bail!("order {client_order_id}: cancel not confirmed");
+        }
+
+        let mut events = Vec::new();
+        match self.client.order(&venue_order_id).await {
+            Ok(status) => {
+                let delta = parse_amount(&status.size_matched) - self.live[client_order_id].matched;
+                if delta > 1e-9 {
+                    events.push(self.fill(client_order_id, parse_amount(&status.price), delta, Liquidity::Maker));
+                }
+            }
+            Err(e) => warn!(client_order_id, error = %format!("{e:#}"), "clob order read after cancel failed; late fills missed"),
+        }
+        self.live.remove(client_order_id);
+        Ok(events)
+    }
+
+    fn open_orders(&self) -> Vec<VenueOrder> {
+        self.live
+            .iter()
+            .map(|(id, l)| VenueOrder {
+                client_order_id: id.clone(),
+                venue_order_id: l.venue_order_id.clone(),
+                market_id: l.order.market_id.clone(),
+                side: l.order.side,
+                outcome: l.order.outcome,
+                price: l.order.price as f64,
+                remaining: (l.order.size as f64 - l.matched).max(0.0),
+            })
+            .collect()
+    }
+
+    /// Balances of the tokens ordered this session.
+    async fn positions(&self) -> Result<Vec<Position>> {
+        let mut positions = Vec::new();
+        for (token_id, (market_id, outcome)) in &self.tokens {
+            let size = from_units(parse_units(&self.client.balance(Some(token_id)).await?.balance)?);
+            if size > 0.0 {
+                positions.push(Position { token_id: token_id.clone(), market_id: market_id.clone(), outcome: *outcome, size });
+            }
+        }
+        Ok(positions)
+    }
+
+    async fn balances(&self) -> Result<Balances> {
+        let cash = from_units(parse_units(&self.client.balance(None).await?.balance)?);
+        Ok(Balances { cash, reserved: reserved(&self.open_orders()) })
+    }
+
+    async fn poll(&mut self) -> Vec<VenueEvent> {
+        let mut events = Vec::new();
+        let ids: Vec<String> = self.live.keys().cloned().collect();
+        for id in ids {
+            let venue_order_id = self.live[&id].venue_order_id.clone();
+            let status = match self.client.order(&venue_order_id).await {
+                Ok(status) => status,
+                Err(e) => {
+                    warn!(client_order_id = %id, error = %format!("{e:#}"), "clob order poll failed");
+                    continue;
+                }
+            };
+            let matched = parse_amount(&status.size_matched);
+            let live = self.live.get_mut(&id).expect("live order");
+            let delta = matched - live.matched;
+            live.matched = matched;
+            if delta > 1e-9 {
+                events.push(self.fill(&id, parse_amount(&status.price), delta, Liquidity::Maker));
+            }
+            match status.status.to_ascii_uppercase().as_str() {
+                "MATCHED" => {
+                    self.live.remove(&id);
+                }
+                "CANCELED" | "CANCELLED" => {
+                    self.live.remove(&id);
+                    events.push(VenueEvent::Cancelled { client_order_id: id, reason: "cancelled at venue".to_string() });
+                }
+                _ => {}
+            }
+        }
+        events
+    }
+
+    fn poll_interval(&self) -> Option<Duration> {
+        Some(self.poll_interval)
+    }
+}
+
+/// Whole-unit decimal string; 0 when missing or malformed.
+fn parse_amount(s: &str) -> f64 {
+    s.parse().unwrap_or(0.0)
+}
+
+/// 1e-6 units from their decimal string.
+fn parse_units(s: &str) -> Result<u128> {
+    s.parse().with_context(|| format!("amount {s:?}"))
+}
+
+/// Whole units from 1e-6 wire units.
+pub fn from_units(v: u128) -> f64 {
+    v as f64 / UNITS
+}
@@ -0,0 +1,253 @@
+use anyhow::{bail, Context, Result};
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use sha3::{Digest, Keccak256};
+
+pub type Address = [u8; 20];
+
+const DOMAIN_TYPE: &str = "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+const ORDER_TYPE: &str = "Order(uint256 salt,address maker,address signer,address taker,uint256 tokenId,\
+    uint256 makerAmount,uint256 takerAmount,uint256 expiration,uint256 nonce,uint256 feeRateBps,uint8 side,\
+    uint8 signatureType)";
+const DOMAIN_NAME: &str = "Polymarket CTF Exchange";
+const DOMAIN_VERSION: &str = "1";
+
+pub const SIDE_BUY: u8 = 0;
+pub const SIDE_SELL: u8 = 1;
+
+/// The order struct the CTF exchange verifies on settlement.
+#[derive(Clone, Debug)]
+pub struct ClobOrder {
+    pub salt: u64,
+    /// Funds the order: the proxy/safe wallet, or the signer for EOA orders.
+    pub maker: Address,
+    pub signer: Address,
+    /// Zero for public orders.
+    pub taker: Address,
+    /// Decimal uint256.
+    pub token_id: String,
+    /// In 1e-6 units of what the maker gives (USDC for buys, outcome tokens for sells).
+    pub maker_amount: u128,
+    pub taker_amount: u128,
+    /// Unix seconds, 0 for none.
+    pub expiration: u64,
+    pub nonce: u64,
+    pub fee_rate_bps: u64,
+    pub side: u8,
+    pub signature_type: u8,
+}
+
+impl ClobOrder {
+    fn struct_hash(&self) -> Result<[u8; 32]> {
+        let mut enc = Vec::with_capacity(13 * 32);
+        enc.extend(keccak256(ORDER_TYPE.as_bytes()));
+        enc.extend(word(self.salt as u128));
+        enc.extend(address_word(&self.maker));
+        enc.extend(address_word(&self.signer));
+        enc.extend(address_word(&self.taker));
+        enc.extend(decimal_word(&self.token_id)?);
+        enc.extend(word(self.maker_amount));
+        enc.extend(word(self.taker_amount));
+        enc.extend(word(self.expiration as u128));
+        enc.extend(word(self.nonce as u128));
+        enc.extend(word(self.fee_rate_bps as u128));
+        enc.extend(word(self.side as u128));
+        enc.extend(word(self.signature_type as u128));
+        Ok(keccak256(&enc))
+    }
+
+    /// `keccak256(0x1901 ‖ domainSeparator ‖ hashStruct(order))`
+    pub fn digest(&self, chain_id: u64, exchange: &Address) -> Result<[u8; 32]> {
+        let mut enc = Vec::with_capacity(2 + 64);
+        enc.extend([0x19, 0x01]);
+        enc.extend(domain_separator(DOMAIN_NAME, DOMAIN_VERSION, chain_id, exchange));
+        enc.extend(self.struct_hash()?);
+        Ok(keccak256(&enc))
+    }
+}
+
+fn domain_separator(name: &str, version: &str, chain_id: u64, contract: &Address) -> [u8; 32] {
+    let mut enc = Vec::with_capacity(5 * 32);
+    enc.extend(keccak256(DOMAIN_TYPE.as_bytes()));
+    enc.extend(keccak256(name.as_bytes()));
+    enc.extend(keccak256(version.as_bytes()));
+    enc.extend(word(chain_id as u128));
+    enc.extend(address_word(contract));
+    keccak256(&enc)
+}
+
+/// secp256k1 key with its Ethereum address.
+pub struct Wallet {
+    key: SigningKey,
+    pub address: Address,
+}
+
+impl Wallet {
+    pub fn from_hex(private_key: &str) -> Result<Self> {
+        let bytes = hex::decode(private_key.trim_start_matches("0x")).context("private key is not hex")?;
+        let key = SigningKey::from_slice(&bytes).context("invalid private key")?;
+        let address = address_of(key.verifying_key());
+        Ok(Self { key, address })
+    }
+
+    /// 65-byte `r ‖ s ‖ v` signature, v in {27, 28}, as 0x-hex.
+    pub fn sign(&self, digest: &[u8; 32]) -> Result<String> {
+        let (sig, recid) = self.key.sign_prehash_recoverable(digest).context("signing")?;
+        let mut bytes = sig.to_bytes().to_vec();
+        bytes.push(27 + recid.to_byte());
+        Ok(format!("0x{}", hex::encode(bytes)))
+    }
+}
+
+/// Address that produced `signature` over `digest`.
+pub fn recover(digest: &[u8; 32], signature: &str) -> Result<Address> {
+    let bytes = hex::decode(signature.trim_start_matches("0x")).context("signature is not hex")?;
+    if bytes.len() != 65 {
+        bail!("signature must be 65 bytes, got {}", bytes.len());
+    }
+    let sig = Signature::from_slice(&bytes[..64]).context("invalid signature")?;
+    let v = bytes[64];
+    let recid = RecoveryId::from_byte(if v >= 27 { v - 27 } else { v }).context("invalid recovery id")?;
+    let key = VerifyingKey::recover_from_prehash(digest, &sig, recid).context("recovering signer")?;
+    Ok(address_of(&key))
+}
+
+fn address_of(key: &VerifyingKey) -> Address {
+    let point = key.to_encoded_point(false);
+    let hash = keccak256(&point.as_bytes()[1..]);
+    hash[12..].try_into().expect("20 bytes")
+}
+
+pub fn parse_address(s: &str) -> Result<Address> {
+    let bytes = hex::decode(s.trim_start_matches("0x")).with_context(|| format!("address {s} is not hex"))?;
+    bytes.try_into().map_err(|_| anyhow::anyhow!("address {s} must be 20 bytes"))
+}
+
+pub fn format_address(a: &Address) -> String {
+    format!("0x{}", hex::encode(a))
+}
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+fn word(v: u128) -> [u8; 32] {
+    let mut w = [0u8; 32];
+    w[16..].copy_from_slice(&v.to_be_bytes());
+    w
+}
+
+fn address_word(a: &Address) -> [u8; 32] {
+    let mut w = [0u8; 32];
+    w[12..].copy_from_slice(a);
+    w
+}
+
+/// Big-endian uint256 from a decimal string; token ids don't fit in u128.
+fn decimal_word(s: &str) -> Result<[u8; 32]> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        bail!("{s:?} is not a decimal integer");
+    }
+    let mut w = [0u8; 32];
+    for digit in s.bytes().map(|b| (b - b'0') as u32) {
+        // w = w * 10 + digit
+        let mut carry = digit;
+        for byte in w.iter_mut().rev() {
+            let v = *byte as u32 * 10 + carry;
+            *byte = v as u8;
+            carry = v >> 8;
+        }
+        if carry != 0 {
+            bail!("{s} overflows uint256");
+        }
+    }
+    Ok(w)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The `Mail` example from the EIP-712 specification.
+    #[test]
+    fn eip712_spec_vector() {
+        let contract = parse_address("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC").unwrap();
+        let domain = domain_separator("Ether Mail", "1", 1, &contract);
+        assert_eq!(hex::encode(domain), "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f");
+
+        let person = |name: &str, wallet: &str| {
+            let mut enc = keccak256(b"Person(string name,address wallet)").to_vec();
+            enc.extend(keccak256(name.as_bytes()));
+            enc.extend(address_word(&parse_address(wallet).unwrap()));
+            keccak256(&enc)
+        };
+        let mut enc = keccak256(b"Mail(Person from,Person to,string contents)Person(string name,address wallet)").to_vec();
+        enc.extend(person("Cow", "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"));
+        enc.extend(person("Bob", "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"));
+        enc.extend(keccak256(b"Hello, Bob!"));
+        let mut msg = vec![0x19, 0x01];
+        msg.extend(domain);
+        msg.extend(keccak256(&enc));
+        let digest = keccak256(&msg);
+        assert_eq!(hex::encode(digest), "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2");
+
+        let wallet = Wallet::from_hex(&hex::encode(keccak256(b"cow"))).unwrap();
+        assert_eq!(format_address(&wallet.address), "0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826");
+        let sig = wallet.sign(&digest).unwrap();
+        assert_eq!(
+            sig,
+            "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d\
+             07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562\
+             1c"
+        );
+        assert_eq!(recover(&digest, &sig).unwrap(), wallet.address);
+    }
+
+    /// Expected digest worked out by hand-encoding `ORDER_TYPE` with an independent keccak.
+    #[test]
+    fn order_digest() {
+        let me = parse_address("0x2c7536e3605d9c16a7a3d7b1898e529396a65c23").unwrap();
+        let order = ClobOrder {
+            salt: 1234567,
+            maker: me,
+            signer: me,
+            taker: [0; 20],
+            token_id: "71321045679252212594626385532706912750332728571942532289631379312455583992563".into(),
+            maker_amount: 6_170_000,
+            taker_amount: 12_340_000,
+            expiration: 0,
+            nonce: 0,
+            fee_rate_bps: 0,
+            side: SIDE_BUY,
+            signature_type: 0,
+        };
+        let exchange = parse_address("0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E").unwrap();
+        assert_eq!(
+            hex::encode(domain_separator(DOMAIN_NAME, DOMAIN_VERSION, 137, &exchange)),
+            "1a573e3617c78403b5b4b892827992f027b03d4eaf570048b8ee8cdd84d151be"
+        );
+        let digest = order.digest(137, &exchange).unwrap();
+        assert_eq!(hex::encode(digest), "769c96a82431cc91669de8245488fd049dd2feee71516ce9c4d01dfe0bb21f98");
+
+        let wallet = Wallet::from_hex("0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318").unwrap();
+        assert_eq!(wallet.address, me);
+        assert_eq!(recover(&digest, &wallet.sign(&digest).unwrap()).unwrap(), me);
+    }
+
+    #[test]
+    fn decimal_words() {
+        assert_eq!(decimal_word("0").unwrap(), [0; 32]);
+        assert_eq!(decimal_word("255").unwrap()[30..], [0, 0xff]);
+        assert_eq!(decimal_word("256").unwrap()[30..], [1, 0]);
+        assert_eq!(
+            hex::encode(
+                decimal_word("71321045679252212594626385532706912750332728571942532289631379312455583992563").unwrap()
+            ),
+            "9dae480511c4c0cb5d6c7937924c1db5be221e758b7135fec2a1977a1c130af3"
+        );
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(decimal_word(max).unwrap(), [0xff; 32]);
+        assert!(decimal_word("115792089237316195423570985008687907853269984665640564039457584007913129639936").is_err());
+        assert!(decimal_word("").is_err());
+        assert!(decimal_word("12a").is_err());
+    }
+}
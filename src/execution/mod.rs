@@ -1 +1,9 @@
 pub mod actor;
+pub mod clob;
+pub mod eip712;
+pub mod orders;
+pub mod paper;
+pub mod policy;
+pub mod slicer;
+pub mod types;
+pub mod venue;
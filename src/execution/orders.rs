@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+use std::time::Instant;
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use crate::core::types::{Execution, Liquidity, Order, OrderStatus, OrderUpdate, TimeInForce};
+
+/// One order as the manager sees it.
+#[derive(Clone, Debug)]
+pub struct OrderState {
+    pub order: Order,
+    pub status: OrderStatus,
+    pub venue_order_id: Option<String>,
+    pub filled: f64,
+    /// Size-weighted over fills.
+    pub avg_px: f64,
+    pub fees: f64,
+    pub submitted_at: Instant,
+}
+
+impl OrderState {
+    pub fn remaining(&self) -> f64 {
+        (self.order.size as f64 - self.filled).max(0.0)
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.status.is_terminal()
+    }
+}
+
+/// Non-terminal orders by client_order_id, as published on the bus' `open_orders` watch.
+pub type OpenOrders = HashMap<String, OrderState>;
+
+/// Tracks orders through New → Acknowledged → PartiallyFilled → Filled / Cancelled / Expired /
+/// Rejected. Terminal orders are kept so a resubmitted client_order_id is still recognized.
+#[derive(Debug, Default)]
+pub struct OrderManager {
+    orders: HashMap<String, OrderState>,
+}
+
+impl OrderManager {
+    /// Registers a new order. `None` if its client_order_id was seen before.
+    pub fn submit(&mut self, order: Order) -> Option<OrderUpdate> {
+        if self.orders.contains_key(&order.client_order_id) {
+            return None;
+        }
+        let state = OrderState {
+            order,
+            status: OrderStatus::New,
+            venue_order_id: None,
+            filled: 0.0,
+            avg_px: 0.0,
+            fees: 0.0,
+            submitted_at: Instant::now(),
+        };
+        let update = update(&state, None);
+        self.orders.insert(state.order.client_order_id.clone(), state);
+        Some(update)
+    }
+
+    pub fn acknowledge(&mut self, client_order_id: &str, venue_order_id: String) -> Result<OrderUpdate> {
+        let state = self.transition(client_order_id, OrderStatus::Acknowledged)?;
+        state.venue_order_id = Some(venue_order_id);
+        Ok(update(state, None))
+    }
+
+    /// Applies one fill. The execution carries this fill alone; the update the cumulative state.
+    pub fn fill(
+        &mut self,
+        client_order_id: &str,
+        price: f64,
+        size: f64,
+        fee: f64,
+        liquidity: Liquidity,
+    ) -> Result<(Execution, OrderUpdate)> {
+        let state = self.get_mut(client_order_id)?;
+        anyhow::ensure!(size > 0.0, "order {client_order_id}: fill size must be > 0");
+        // small tolerance for f32 sizes
+        anyhow::ensure!(
+            size <= state.remaining() + 1e-6,
+            "order {client_order_id}: fill of {size} exceeds remaining {}",
+            state.remaining()
+        );
+        let to = if size >= state.remaining() - 1e-6 { OrderStatus::Filled } else { OrderStatus::PartiallyFilled };
+        let state = self.transition(client_order_id, to)?;
+        state.avg_px = (state.avg_px * state.filled + price * size) / (state.filled + size);
+        state.filled += size;
+        state.fees += fee;
+
+        let order = &state.order;
+        let ts_ms = Utc::now().timestamp_millis();
+        let execution = Execution {
+            client_order_id: order.client_order_id.clone(),
+            venue_order_id: state.venue_order_id.clone().unwrap_or_default(),
+            market_id: order.market_id.clone(),
+            side: order.side,
+            outcome: order.outcome,
+            liquidity,
+            status: state.status,
+            avg_px: price as f32,
+            filled: size as f32,
+            fee: fee as f32,
+            ts_ms,
+        };
+        Ok((execution, update(state, None)))
+    }
+
+    pub fn cancel(&mut self, client_order_id: &str, reason: &str) -> Result<OrderUpdate> {
+        let state = self.transition(client_order_id, OrderStatus::Cancelled)?;
+        Ok(update(state, Some(reason)))
+    }
+
+    pub fn expire(&mut self, client_order_id: &str) -> Result<OrderUpdate> {
+        let state = self.transition(client_order_id, OrderStatus::Expired)?;
+        Ok(update(state, Some("ttl elapsed")))
+    }
+
+    pub fn reject(&mut self, client_order_id: &str, reason: &str) -> Result<OrderUpdate> {
+        let state = self.transition(client_order_id, OrderStatus::Rejected)?;
+        Ok(update(state, Some(reason)))
+    }
+
+    /// Open `Gtd` orders past their ttl.
+    pub fn expired(&self) -> Vec<String> {
+        self.orders
+            .values()
+            .filter(|s| s.is_open() && s.order.time_in_force == TimeInForce::Gtd)
+            .filter(|s| s.order.ttl.is_some_and(|ttl| s.submitted_at.elapsed() >= ttl))
+            .map(|s| s.order.client_order_id.clone())
+            .collect()
+    }
+
+    pub fn get(&self, client_order_id: &str) -> Option<&OrderState> {
+        self.orders.get(client_order_id)
+    }
+
+    pub fn open(&self) -> OpenOrders {
+        self.orders
+            .iter()
+            .filter(|(_, s)| s.is_open())
+            .map(|(id, s)| (id.clone(), s.clone()))
+            .collect()
+    }
+
+    fn get_mut(&mut self, client_order_id: &str) -> Result<&mut OrderState> {
+        self.orders.get_mut(client_order_id).with_context(|| format!("unknown order {client_order_id}"))
+    }
+
+    fn transition(&mut self, client_order_id: &str, to: OrderStatus) -> Result<&mut OrderState> {
+        let state = self.get_mut(client_order_id)?;
+        if !allowed(state.status, to) {
+            bail!("order {client_order_id}: illegal transition {:?} -> {to:?}", state.status);
+        }
+        state.status = to;
+        Ok(state)
+    }
+}
+
+fn allowed(from: OrderStatus, to: OrderStatus) -> bool {
+    use OrderStatus::*;
+    match (from, to) {
+        (New, Acknowledged | Rejected) => true,
+        // venues may report a match before (or instead of) the ack
+        (New | Acknowledged | PartiallyFilled, PartiallyFilled | Filled | Cancelled | Expired) => true,
+        _ => false,
+    }
+}
+
+fn update(state: &OrderState, reason: Option<&str>) -> OrderUpdate {
+    OrderUpdate {
+        client_order_id: state.order.client_order_id.clone(),
+        venue_order_id: state.venue_order_id.clone(),
+        market_id: state.order.market_id.clone(),
+        status: state.status,
+        filled: state.filled as f32,
+        remaining: state.remaining() as f32,
+        avg_px: state.avg_px as f32,
+        reason: reason.map(str::to_string),
+        ts_ms: Utc::now().timestamp_millis(),
+    }
+}
+
+/// Checks the venue would reject anyway.
+pub fn validate(order: &Order) -> Result<(), String> {
+    if order.size <= 0.0 {
+        return Err(format!("size {} must be > 0", order.size));
+    }
+    if !(order.price > 0.0 && order.price < 1.0) {
+        return Err(format!("price {} must be within (0, 1)", order.price));
+    }
+    if order.token_id.is_empty() {
+        return Err("missing token id".to_string());
+    }
+    if order.effective_time_in_force() == TimeInForce::Gtd && order.ttl.is_none() {
+        return Err("Gtd order without ttl".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::*;
+    use crate::core::types::{OrderType, Outcome, Side};
+
+    /// A GTD buy of 10 YES at 0.5.
+    fn order(id: &str) -> Order {
+        Order {
+            client_order_id: id.into(),
+            market_id: "m".into(),
+            side: Side::Buy,
+            outcome: Outcome::Yes,
+            token_id: "1".into(),
+            order_type: OrderType::Limit,
+            time_in_force: TimeInForce::Gtd,
+            ttl: Some(Duration::from_secs(60)),
+            post_only: false,
+            neg_risk: false,
+            price: 0.5,
+            size: 10.0,
+            intent: None,
+        }
+    }
+
+    /// A manager holding `a`, acknowledged as `v1`.
+    fn acknowledged() -> OrderManager {
+        let mut m = OrderManager::default();
+        assert_eq!(m.submit(order("a")).unwrap().status, OrderStatus::New);
+        assert_eq!(m.acknowledge("a", "v1".into()).unwrap().status, OrderStatus::Acknowledged);
+        m
+    }
+
+    #[test]
+    fn fills_accumulate_to_filled() {
+        let mut m = acknowledged();
+        let (execution, update) = m.fill("a", 0.5, 4.0, 0.01, Liquidity::Maker).unwrap();
+        assert_eq!((execution.venue_order_id.as_str(), execution.filled, execution.status), ("v1", 4.0, OrderStatus::PartiallyFilled));
+        assert_eq!((update.filled, update.remaining), (4.0, 6.0));
+
+        let (execution, update) = m.fill("a", 0.6, 6.0, 0.01, Liquidity::Taker).unwrap();
+        // the execution is this fill alone, the update the whole order
+        assert_eq!((execution.avg_px, execution.filled), (0.6, 6.0));
+        assert_eq!((update.status, update.filled, update.remaining), (OrderStatus::Filled, 10.0, 0.0));
+        assert!((update.avg_px - 0.56).abs() < 1e-6);
+        assert!((m.get("a").unwrap().fees - 0.02).abs() < 1e-9);
+        assert!(m.open().is_empty());
+    }
+
+    #[test]
+    fn overfill_is_refused_and_changes_nothing() {
+        let mut m = acknowledged();
+        m.fill("a", 0.5, 4.0, 0.0, Liquidity::Maker).unwrap();
+        assert!(m.fill("a", 0.5, 6.5, 0.0, Liquidity::Maker).is_err());
+        assert!(m.fill("a", 0.5, 0.0, 0.0, Liquidity::Maker).is_err());
+        let state = m.get("a").unwrap();
+        assert_eq!((state.status, state.filled), (OrderStatus::PartiallyFilled, 4.0));
+    }
+
+    #[test]
+    fn nothing_fills_after_a_cancel() {
+        let mut m = acknowledged();
+        m.fill("a", 0.5, 4.0, 0.0, Liquidity::Maker).unwrap();
+        let update = m.cancel("a", "requested").unwrap();
+        assert_eq!((update.status, update.reason.as_deref()), (OrderStatus::Cancelled, Some("requested")));
+        assert!(m.fill("a", 0.5, 1.0, 0.0, Liquidity::Maker).is_err());
+        assert!(m.expire("a").is_err());
+        assert_eq!(m.get("a").unwrap().filled, 4.0);
+    }
+
+    #[test]
+    fn acknowledgement_only_from_new() {
+        let mut m = acknowledged();
+        assert!(m.acknowledge("a", "v2".into()).is_err());
+        assert_eq!(m.get("a").unwrap().venue_order_id.as_deref(), Some("v1"));
+
+        // a fill may beat the ack, which then has nothing to do
+        m.submit(order("b")).unwrap();
+        m.fill("b", 0.5, 4.0, 0.0, Liquidity::Taker).unwrap();
+        assert!(m.acknowledge("b", "v3".into()).is_err());
+        // and once acknowledged the venue can't reject it
+        assert!(m.reject("a", "late").is_err());
+    }
+
+    #[test]
+    fn client_order_ids_are_never_reused() {
+        let mut m = acknowledged();
+        m.cancel("a", "requested").unwrap();
+        assert!(m.submit(order("a")).is_none());
+        assert!(m.fill("unknown", 0.5, 1.0, 0.0, Liquidity::Maker).is_err());
+    }
+
+    #[test]
+    fn expired_lists_open_gtd_orders_past_their_ttl() {
+        let mut m = OrderManager::default();
+        m.submit(Order { ttl: Some(Duration::ZERO), ..order("due") });
+        m.submit(order("later"));
+        m.submit(Order { time_in_force: TimeInForce::Gtc, ttl: Some(Duration::ZERO), ..order("gtc") });
+        m.submit(Order { ttl: Some(Duration::ZERO), ..order("done") });
+        m.cancel("done", "requested").unwrap();
+        assert_eq!(m.expired(), ["due"]);
+        assert_eq!(m.expire("due").unwrap().status, OrderStatus::Expired);
+    }
+}
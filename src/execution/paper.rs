@@ -0,0 +1,471 @@
+use std::collections::HashMap;
+use anyhow::{bail, Result};
+use crate::config::config::{FeesCfg, PaperCfg};
+use crate::core::types::{Liquidity, MarketDataSnap, Order, Outcome, Side, TimeInForce};
+use crate::execution::types::{Balances, Position, VenueEvent, VenueOrder};
+use crate::execution::venue::{reserved, Venue};
+
+/// Prices within this are the same level (f32 prices, and NO prices derived as 1 - YES).
+const PRICE_EPS: f64 = 1e-6;
+
+#[derive(Clone, Copy, Debug)]
+struct Level {
+    price: f64,
+    size: f64,
+}
+
+/// Top of the YES book from the latest snapshot, less what paper orders have taken since.
+#[derive(Clone, Copy, Debug, Default)]
+struct Book {
+    bid: Option<Level>,
+    ask: Option<Level>,
+}
+
+impl Book {
+    fn from_snap(snap: &MarketDataSnap) -> Self {
+        let level = |price: f32, size: f32| {
+            let (price, size) = (price as f64, size as f64);
+            (price > 0.0 && price < 1.0 && size > 0.0).then_some(Level { price, size })
+        };
+        Self { bid: level(snap.best_bid, snap.bid_size), ask: level(snap.best_ask, snap.ask_size) }
+    }
+
+    /// The YES level an order trades against (`take`) or joins, and whether the order's
+    /// prices are 1 - YES prices.
+    fn slot(&mut self, side: Side, outcome: Outcome, take: bool) -> (&mut Option<Level>, bool) {
+        // buying NO is selling YES, so it takes the YES bid and rests against the YES ask
+        let takes_ask = (side == Side::Buy) == (outcome == Outcome::Yes);
+        let level = if takes_ask == take { &mut self.ask } else { &mut self.bid };
+        (level, outcome == Outcome::No)
+    }
+
+    /// Best level in the order's own terms (outcome price and size).
+    fn level(&mut self, side: Side, outcome: Outcome, take: bool) -> Option<Level> {
+        let (level, flip) = self.slot(side, outcome, take);
+        level.map(|l| Level { price: if flip { 1.0 - l.price } else { l.price }, size: l.size })
+    }
+
+    fn consume(&mut self, side: Side, outcome: Outcome, size: f64) {
+        let (level, _) = self.slot(side, outcome, true);
+        if let Some(l) = level {
+            l.size -= size;
+            if l.size <= 0.0 {
+                *level = None;
+            }
+        }
+    }
+}
+
+/// A limit order resting at the paper venue.
+#[derive(Clone, Debug)]
+struct Resting {
+    order: Order,
+    venue_order_id: String,
+    remaining: f64,
+    /// Size queued at our price ahead of us; infinite while our price is behind the touch
+    /// and the level can't be seen.
+    queue_ahead: f64,
+}
+
+/// Simulated venue: orders match against the latest `MarketDataSnap` of their market.
+/// Only the top of book is visible, so an order takes at most the touch size. Resting orders
+/// fill when the opposite side crosses them, or when size leaves their level ahead of them
+/// (assumed traded, front of the queue first). Buys costing more than the cash not already
+/// reserved by resting buys, and sells of more tokens than are held and not already offered,
+/// are rejected.
+pub struct PaperVenue {
+    fees: FeesCfg,
+    books: HashMap<String, Book>,
+    resting: HashMap<String, Resting>,
+    next_id: u64,
+    cash: f64,
+    /// By token id.
+    positions: HashMap<String, Position>,
+}
+
+impl PaperVenue {
+    pub fn new(fees: &FeesCfg, cfg: &PaperCfg) -> Self {
+        Self {
+            fees: fees.clone(),
+            books: HashMap::new(),
+            resting: HashMap::new(),
+            next_id: 0,
+            cash: cfg.balance,
+            positions: HashMap::new(),
+        }
+    }
+
+    fn submit(&mut self, order: &Order) -> Vec<VenueEvent> {
+        let id = order.client_order_id.clone();
+        let reject = |reason: &str| vec![VenueEvent::Rejected { client_order_id: id.clone(), reason: reason.to_string() }];
+        let price = order.price as f64;
+        match order.side {
+            Side::Buy => {
+                // the whole order at its limit, with the taker fee in case it crosses
+                let cost = price * order.size as f64 + self.fee(price, order.size as f64, Liquidity::Taker);
+                let free = self.cash - reserved(&self.open_orders());
+                if cost > free + PRICE_EPS {
+                    return reject(&format!("insufficient cash: needs {cost:.2}, {free:.2} free"));
+                }
+            }
+            Side::Sell => {
+                let free = self.unoffered(&order.token_id);
+                if order.size as f64 > free + PRICE_EPS {
+                    return reject(&format!("insufficient position: selling {}, {free:.2} free", order.size));
+                }
+            }
+        }
+        let Some(book) = self.books.get_mut(&order.market_id) else { return reject("no market data") };
+        let mut remaining = order.size as f64;
+        let crossing = book
+            .level(order.side, order.outcome, true)
+            .filter(|l| crosses(order.side, price, l.price));
+        if order.post_only && crossing.is_some() {
+            return reject("post-only order would cross");
+        }
+
+        self.next_id += 1;
+        let venue_order_id = format!("paper-{}", self.next_id);
+        let mut events = vec![VenueEvent::Acknowledged { client_order_id: id.clone(), venue_order_id: venue_order_id.clone() }];
+        if order.effective_time_in_force() == TimeInForce::Fok && crossing.is_none_or(|l| l.size < remaining) {
+            events.push(VenueEvent::Cancelled { client_order_id: id, reason: "fill-or-kill not fillable".to_string() });
+            return events;
+        }
+        if let Some(level) = crossing {
+            let size = remaining.min(level.size);
+            book.consume(order.side, order.outcome, size);
+            remaining -= size;
+            events.push(self.fill(order, level.price, size, Liquidity::Taker));
+        }
+        if remaining <= 0.0 {
+            return events;
+        }
+
+        if order.is_immediate() {
+            events.push(VenueEvent::Cancelled { client_order_id: id, reason: "unfilled remainder".to_string() });
+            return events;
+        }
+        let queue_ahead = self
+            .books
+            .get_mut(&order.market_id)
+            .and_then(|b| b.level(order.side, order.outcome, false))
+            .map_or(0.0, |l| queue_at(order.side, price, l));
+        self.resting.insert(id, Resting { order: order.clone(), venue_order_id, remaining, queue_ahead });
+        events
+    }
+
+    /// Replaces the market's book and matches resting orders against it.
+    fn match_book(&mut self, snap: &MarketDataSnap) -> Vec<VenueEvent> {
+        let mut book = Book::from_snap(snap);
+        let previous = self.books.get(&snap.market_id).copied();
+        let mut fills = Vec::new();
+        let mut ids: Vec<&String> = self.resting.iter().filter(|(_, r)| r.order.market_id == snap.market_id).map(|(id, _)| id).collect();
+        // oldest venue ids first would be fairer; client ids at least make it deterministic
+        ids.sort();
+        let ids: Vec<String> = ids.into_iter().cloned().collect();
+
+        for id in ids {
+            let r = self.resting.get_mut(&id).expect("resting order");
+            let (side, outcome, price) = (r.order.side, r.order.outcome, r.order.price as f64);
+            let size = match book.level(side, outcome, true) {
+                // the other side came through our price
+                Some(l) if crosses(side, price, l.price) => {
+                    let size = r.remaining.min(l.size);
+                    book.consume(side, outcome, size);
+                    size
+                }
+                _ => match book.level(side, outcome, false) {
+                    Some(l) if same(l.price, price) => {
+                        let before = previous
+                            .and_then(|mut b| b.level(side, outcome, false))
+                            .filter(|p| same(p.price, price))
+                            .map(|p| p.size);
+                        match before {
+                            Some(before) if r.queue_ahead.is_finite() => {
+                                r.queue_ahead -= (before - l.size).max(0.0);
+                                let size = (-r.queue_ahead).clamp(0.0, r.remaining);
+                                r.queue_ahead = r.queue_ahead.max(0.0);
+                                size
+                            }
+                            // our level just became the touch: join behind what is there
+                            _ => {
+                                r.queue_ahead = r.queue_ahead.min(l.size);
+                                0.0
+                            }
+                        }
+                    }
+                    Some(l) => {
+                        r.queue_ahead = queue_at(side, price, l).min(r.queue_ahead);
+                        0.0
+                    }
+                    None => {
+                        r.queue_ahead = 0.0;
+                        0.0
+                    }
+                },
+            };
+            if size > 0.0 {
+                r.remaining -= size;
+                fills.push((id, r.order.clone(), price, size));
+            }
+        }
+        self.books.insert(snap.market_id.clone(), book);
+
+        let mut events = Vec::with_capacity(fills.len());
+        for (id, order, price, size) in fills {
+            if self.resting.get(&id).is_some_and(|r| r.remaining <= 0.0) {
+                self.resting.remove(&id);
+            }
+            events.push(self.fill(&order, price, size, Liquidity::Maker));
+        }
+        events
+    }
+
+    fn fee(&self, price: f64, size: f64, liquidity: Liquidity) -> f64 {
+        let bps = match liquidity {
+            Liquidity::Maker => self.fees.maker_bps,
+            Liquidity::Taker => self.fees.taker_bps,
+        };
+        bps / 10_000.0 * price.min(1.0 - price) * size
+    }
+
+    /// Tokens held and not already offered by resting sells.
+    fn unoffered(&self, token_id: &str) -> f64 {
+        let held = self.positions.get(token_id).map_or(0.0, |p| p.size);
+        let offered: f64 = self
+            .resting
+            .values()
+            .filter(|r| r.order.side == Side::Sell && r.order.token_id == token_id)
+            .map(|r| r.remaining)
+            .sum();
+        held - offered
+    }
+
+    /// Books a fill against cash and positions.
+    fn fill(&mut self, order: &Order, price: f64, size: f64, liquidity: Liquidity) -> VenueEvent {
+        let fee = self.fee(price, size, liquidity);
+        let signed = if order.side == Side::Buy { size } else { -size };
+        self.cash -= signed * price + fee;
+        self.positions
+            .entry(order.token_id.clone())
+            .or_insert_with(|| Position {
+                token_id: order.token_id.clone(),
+                market_id: order.market_id.clone(),
+                outcome: order.outcome,
+                size: 0.0,
+            })
+            .size += signed;
+        VenueEvent::Fill { client_order_id: order.client_order_id.clone(), price, size, fee, liquidity }
+    }
+}
+
+#[async_trait::async_trait]
+impl Venue for PaperVenue {
+    fn name(&self) -> &'static str {
+        "paper"
+    }
+
+    async fn place(&mut self, order: &Order) -> Vec<VenueEvent> {
+        self.submit(order)
+    }
+
+    /// Resting orders only fill on `on_book`, so nothing can race the cancel.
+    async fn cancel(&mut self, client_order_id: &str) -> Result<Vec<VenueEvent>> {
+        if self.resting.remove(client_order_id).is_none() {
+            bail!("order {client_order_id} is not resting");
+        }
+        Ok(Vec::new())
+    }
+
+    fn open_orders(&self) -> Vec<VenueOrder> {
+        self.resting
+            .iter()
+            .map(|(id, r)| VenueOrder {
+                client_order_id: id.clone(),
+                venue_order_id: r.venue_order_id.clone(),
+                market_id: r.order.market_id.clone(),
+                side: r.order.side,
+                outcome: r.order.outcome,
+                price: r.order.price as f64,
+                remaining: r.remaining,
+            })
+            .collect()
+    }
+
+    async fn positions(&self) -> Result<Vec<Position>> {
+        Ok(self.positions.values().filter(|p| p.size.abs() > 1e-9).cloned().collect())
+    }
+
+    async fn balances(&self) -> Result<Balances> {
+        Ok(Balances { cash: self.cash, reserved: reserved(&self.open_orders()) })
+    }
+
+    async fn on_book(&mut self, snap: &MarketDataSnap) -> Vec<VenueEvent> {
+        self.match_book(snap)
+    }
+}
+
+/// Whether a level at `level` is marketable for an order limited at `limit`.
+fn crosses(side: Side, limit: f64, level: f64) -> bool {
+    match side {
+        Side::Buy => level <= limit + PRICE_EPS,
+        Side::Sell => level >= limit - PRICE_EPS,
+    }
+}
+
+/// Queue ahead of a new order at `price` given the best level on its own side.
+fn queue_at(side: Side, price: f64, best: Level) -> f64 {
+    let improves = match side {
+        Side::Buy => price > best.price + PRICE_EPS,
+        Side::Sell => price < best.price - PRICE_EPS,
+    };
+    if same(price, best.price) {
+        best.size
+    } else if improves {
+        0.0
+    } else {
+        f64::INFINITY
+    }
+}
+
+fn same(a: f64, b: f64) -> bool {
+    (a - b).abs() <= PRICE_EPS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::OrderType;
+
+    fn venue(balance: f64) -> PaperVenue {
+        PaperVenue::new(&FeesCfg::default(), &PaperCfg { balance })
+    }
+
+    fn snap(bid: f32, bid_size: f32, ask: f32, ask_size: f32) -> MarketDataSnap {
+        MarketDataSnap { market_id: "m".into(), book_ts_ms: 0, best_bid: bid, best_ask: ask, bid_size, ask_size }
+    }
+
+    fn order(id: &str, side: Side, outcome: Outcome, time_in_force: TimeInForce, price: f32, size: f32) -> Order {
+        Order {
+            client_order_id: id.into(),
+            market_id: "m".into(),
+            side,
+            outcome,
+            token_id: format!("{outcome:?}"),
+            order_type: OrderType::Limit,
+            time_in_force,
+            ttl: None,
+            post_only: false,
+            neg_risk: false,
+            price,
+            size,
+            intent: None,
+        }
+    }
+
+    /// Events in short form, e.g. `fill 30@0.52`.
+    fn show(events: &[VenueEvent]) -> Vec<String> {
+        events
+            .iter()
+            .map(|e| match e {
+                VenueEvent::Acknowledged { .. } => "ack".to_string(),
+                VenueEvent::Fill { price, size, .. } => format!("fill {size}@{price:.2}"),
+                VenueEvent::Cancelled { reason, .. } => format!("cancel: {reason}"),
+                VenueEvent::Rejected { reason, .. } => format!("reject: {reason}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fok_needs_the_whole_size_at_the_touch() {
+        let mut v = venue(100.0);
+        v.match_book(&snap(0.48, 100.0, 0.52, 30.0));
+        let killed = v.submit(&order("a", Side::Buy, Outcome::Yes, TimeInForce::Fok, 0.55, 40.0));
+        assert_eq!(show(&killed), ["ack", "cancel: fill-or-kill not fillable"]);
+        assert_eq!(v.cash, 100.0);
+
+        let filled = v.submit(&order("b", Side::Buy, Outcome::Yes, TimeInForce::Fok, 0.55, 30.0));
+        assert_eq!(show(&filled), ["ack", "fill 30@0.52"]);
+        assert!((v.cash - (100.0 - 30.0 * 0.52)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn fak_takes_the_touch_and_cancels_the_rest() {
+        let mut v = venue(100.0);
+        v.match_book(&snap(0.48, 100.0, 0.52, 30.0));
+        let events = v.submit(&order("a", Side::Buy, Outcome::Yes, TimeInForce::Fak, 0.55, 40.0));
+        assert_eq!(show(&events), ["ack", "fill 30@0.52", "cancel: unfilled remainder"]);
+        assert!(v.resting.is_empty());
+
+        // the touch it took is gone until the next snapshot
+        let events = v.submit(&order("b", Side::Buy, Outcome::Yes, TimeInForce::Fak, 0.55, 10.0));
+        assert_eq!(show(&events), ["ack", "cancel: unfilled remainder"]);
+    }
+
+    #[test]
+    fn no_prices_are_the_flipped_yes_book() {
+        let mut book = Book::from_snap(&snap(0.40, 50.0, 0.45, 60.0));
+        // buying NO takes the YES bid and joins against the YES ask
+        let take = book.level(Side::Buy, Outcome::No, true).unwrap();
+        let join = book.level(Side::Buy, Outcome::No, false).unwrap();
+        assert!((take.price - 0.60).abs() < 1e-6 && take.size == 50.0);
+        assert!((join.price - 0.55).abs() < 1e-6 && join.size == 60.0);
+        // selling NO is the other way round
+        let take = book.level(Side::Sell, Outcome::No, true).unwrap();
+        assert!((take.price - 0.55).abs() < 1e-6 && take.size == 60.0);
+
+        let mut v = venue(100.0);
+        v.match_book(&snap(0.40, 50.0, 0.45, 60.0));
+        let events = v.submit(&order("a", Side::Buy, Outcome::No, TimeInForce::Fak, 0.61, 10.0));
+        assert_eq!(show(&events), ["ack", "fill 10@0.60"]);
+        assert_eq!(v.books["m"].bid.unwrap().size, 40.0);
+    }
+
+    #[test]
+    fn resting_order_fills_once_the_queue_ahead_trades() {
+        let mut v = venue(100.0);
+        v.match_book(&snap(0.48, 100.0, 0.52, 100.0));
+        assert_eq!(show(&v.submit(&order("a", Side::Buy, Outcome::Yes, TimeInForce::Gtc, 0.48, 20.0))), ["ack"]);
+        assert_eq!(v.resting["a"].queue_ahead, 100.0);
+
+        // 30 ahead of us traded
+        assert!(v.match_book(&snap(0.48, 70.0, 0.52, 100.0)).is_empty());
+        assert_eq!(v.resting["a"].queue_ahead, 70.0);
+        // size joining the level queues behind us
+        assert!(v.match_book(&snap(0.48, 150.0, 0.52, 100.0)).is_empty());
+        assert_eq!(v.resting["a"].queue_ahead, 70.0);
+        // 85 trades: the 70 ahead, then 15 of ours
+        assert_eq!(show(&v.match_book(&snap(0.48, 65.0, 0.52, 100.0))), ["fill 15@0.48"]);
+        assert_eq!(v.resting["a"].remaining, 5.0);
+        // the ask coming through our price fills the rest
+        assert_eq!(show(&v.match_book(&snap(0.46, 65.0, 0.47, 100.0))), ["fill 5@0.48"]);
+        assert!(v.resting.is_empty());
+    }
+
+    #[test]
+    fn buys_need_cash_not_already_reserved() {
+        let mut v = venue(20.0);
+        v.match_book(&snap(0.48, 100.0, 0.52, 100.0));
+        let events = v.submit(&order("a", Side::Buy, Outcome::Yes, TimeInForce::Gtc, 0.50, 50.0));
+        assert_eq!(show(&events), ["reject: insufficient cash: needs 25.00, 20.00 free"]);
+
+        assert_eq!(show(&v.submit(&order("b", Side::Buy, Outcome::Yes, TimeInForce::Gtc, 0.40, 30.0))), ["ack"]);
+        let events = v.submit(&order("c", Side::Buy, Outcome::Yes, TimeInForce::Gtc, 0.40, 30.0));
+        assert_eq!(show(&events), ["reject: insufficient cash: needs 12.00, 8.00 free"]);
+    }
+
+    #[test]
+    fn sells_need_tokens_not_already_offered() {
+        let mut v = venue(100.0);
+        v.match_book(&snap(0.48, 100.0, 0.52, 100.0));
+        let events = v.submit(&order("a", Side::Sell, Outcome::Yes, TimeInForce::Fak, 0.45, 10.0));
+        assert_eq!(show(&events), ["reject: insufficient position: selling 10, 0.00 free"]);
+
+        v.submit(&order("b", Side::Buy, Outcome::Yes, TimeInForce::Fak, 0.52, 30.0));
+        assert_eq!(show(&v.submit(&order("c", Side::Sell, Outcome::Yes, TimeInForce::Gtc, 0.55, 20.0))), ["ack"]);
+        let events = v.submit(&order("d", Side::Sell, Outcome::Yes, TimeInForce::Fak, 0.45, 20.0));
+        assert_eq!(show(&events), ["reject: insufficient position: selling 20, 10.00 free"]);
+        assert_eq!(show(&v.submit(&order("e", Side::Sell, Outcome::Yes, TimeInForce::Fak, 0.45, 10.0))), ["ack", "fill 10@0.48"]);
+        assert_eq!(v.positions["Yes"].size, 20.0);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::config::config::{FeesCfg, PolicyCfg};
+use crate::core::types::{MarketDataSnap, Order, OrderIntent, Outcome, Side, Urgency};
+
+/// Prices within this are equal (book prices are f32).
+const PRICE_EPS: f64 = 1e-6;
+/// Fractions of a tick this small are f32 noise, not a price between ticks.
+const TICK_EPS: f64 = 1e-3;
+
+/// Where an order with an intent goes on the book.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Placement {
+    /// Take liquidity at the touch.
+    Cross { price: f64 },
+    /// Rest at `price` without crossing.
+    Quote { price: f64 },
+}
+
+/// Turns an order's intent and the current book into a limit price.
+pub trait ExecutionPolicy: Send + Sync {
+    fn place(&self, order: &Order, intent: &OrderIntent, book: Option<&MarketDataSnap>) -> Placement;
+}
+
+/// Two-sided book in an outcome's own prices.
+#[derive(Clone, Copy, Debug)]
+pub struct Touch {
+    pub bid: f64,
+    pub ask: f64,
+}
+
+impl Touch {
+    /// `None` unless 0 < bid <= ask < 1. NO prices are one minus the opposite YES side.
+    pub fn of(snap: &MarketDataSnap, outcome: Outcome) -> Option<Self> {
+        let (bid, ask) = (snap.best_bid as f64, snap.best_ask as f64);
+        let (bid, ask) = match outcome {
+            Outcome::Yes => (bid, ask),
+            Outcome::No => (1.0 - ask, 1.0 - bid),
+        };
+        (bid > 0.0 && bid <= ask && ask < 1.0).then_some(Self { bid, ask })
+    }
+}
+
+/// Quote one tick inside the spread by default; cross only when the edge left after paying
+/// half the spread and the taker fee clears `crossThreshold` (any positive edge when urgent,
+/// never when passive), and the touch is within the intent's max price.
+pub struct LimitFirst {
+    cross_threshold: f64,
+    tick: f64,
+    taker_bps: f64,
+}
+
+impl LimitFirst {
+    pub fn new(cfg: &PolicyCfg, fees: &FeesCfg) -> Self {
+        Self { cross_threshold: cfg.cross_threshold, tick: cfg.tick, taker_bps: fees.taker_bps }
+    }
+
+    /// Edge per contract left after taking at `price` rather than the target.
+    fn edge_after_crossing(&self, side: Side, intent: &OrderIntent, price: f64) -> f64 {
+        let target = intent.target_price as f64;
+        let slippage = match side {
+            Side::Buy => price - target,
+            Side::Sell => target - price,
+        };
+        let fee = self.taker_bps / 10_000.0 * price.min(1.0 - price);
+        intent.edge - slippage - fee
+    }
+
+    fn to_tick(&self, price: f64, side: Side) -> f64 {
+        let ticks = price / self.tick;
+        let ticks = if (ticks - ticks.round()).abs() < TICK_EPS {
+            ticks.round()
+        } else {
+            // round away from the touch we would cross
+            match side {
+                Side::Buy => ticks.floor(),
+                Side::Sell => ticks.ceil(),
+            }
+        };
+        (ticks * self.tick).clamp(self.tick, 1.0 - self.tick)
+    }
+}
+
+impl ExecutionPolicy for LimitFirst {
+    fn place(&self, order: &Order, intent: &OrderIntent, book: Option<&MarketDataSnap>) -> Placement {
+        let max = intent.max_price as f64;
+        let Some(touch) = book.and_then(|snap| Touch::of(snap, order.outcome)) else {
+            // blind: rest at the target, within the limit
+            let price = match order.side {
+                Side::Buy => (intent.target_price as f64).min(max),
+                Side::Sell => (intent.target_price as f64).max(max),
+            };
+            return Placement::Quote { price: self.to_tick(price, order.side) };
+        };
+
+        let (take, within_max) = match order.side {
+            Side::Buy => (touch.ask, touch.ask <= max + PRICE_EPS),
+            Side::Sell => (touch.bid, touch.bid >= max - PRICE_EPS),
+        };
+        let threshold = match intent.urgency {
+            Urgency::Passive => f64::INFINITY,
+            Urgency::Normal => self.cross_threshold,
+            Urgency::Urgent => PRICE_EPS,
+        };
+        if within_max && self.edge_after_crossing(order.side, intent, take) >= threshold {
+            return Placement::Cross { price: take };
+        }
+
+        // improve the touch by a tick unless passive, without locking or crossing the book
+        let improve = if intent.urgency == Urgency::Passive { 0.0 } else { self.tick };
+        let price = match order.side {
+            Side::Buy => (touch.bid + improve).min(touch.ask - self.tick).max(touch.bid).min(max),
+            Side::Sell => (touch.ask - improve).max(touch.bid + self.tick).min(touch.ask).max(max),
+        };
+        Placement::Quote { price: self.to_tick(price, order.side) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{OrderType, TimeInForce};
+
+    fn policy(taker_bps: f64) -> LimitFirst {
+        LimitFirst::new(&PolicyCfg { cross_threshold: 0.01, tick: 0.01 }, &FeesCfg { maker_bps: 0.0, taker_bps })
+    }
+
+    fn order(side: Side, outcome: Outcome) -> Order {
+        Order {
+            client_order_id: "a".into(),
+            market_id: "m".into(),
+            side,
+            outcome,
+            token_id: "1".into(),
+            order_type: OrderType::Limit,
+            time_in_force: TimeInForce::Gtc,
+            ttl: None,
+            post_only: false,
+            neg_risk: false,
+            price: 0.5,
+            size: 10.0,
+            intent: None,
+        }
+    }
+
+    fn intent(target_price: f32, max_price: f32, urgency: Urgency, edge: f64) -> OrderIntent {
+        OrderIntent { target_price, max_price, urgency, edge }
+    }
+
+    fn book(bid: f32, ask: f32) -> MarketDataSnap {
+        MarketDataSnap { market_id: "m".into(), book_ts_ms: 0, best_bid: bid, best_ask: ask, bid_size: 100.0, ask_size: 100.0 }
+    }
+
+    /// `cross 0.52` or `quote 0.49`.
+    fn show(placement: Placement) -> String {
+        match placement {
+            Placement::Cross { price } => format!("cross {price:.2}"),
+            Placement::Quote { price } => format!("quote {price:.2}"),
+        }
+    }
+
+    fn place(p: &LimitFirst, order: &Order, intent: &OrderIntent, book: Option<&MarketDataSnap>) -> String {
+        show(p.place(order, intent, book))
+    }
+
+    #[test]
+    fn thin_edge_rests_inside_the_spread() {
+        let buy = order(Side::Buy, Outcome::Yes);
+        // 0.02 of edge pays 0.02 of slippage to the ask
+        assert_eq!(place(&policy(0.0), &buy, &intent(0.50, 0.58, Urgency::Normal, 0.02), Some(&book(0.48, 0.52))), "quote 0.49");
+        // a one-tick spread leaves nothing inside it, so join the bid
+        assert_eq!(place(&policy(0.0), &buy, &intent(0.50, 0.58, Urgency::Normal, 0.0), Some(&book(0.49, 0.50))), "quote 0.49");
+        // passive joins rather than improves, whatever the edge
+        assert_eq!(place(&policy(0.0), &buy, &intent(0.50, 0.58, Urgency::Passive, 0.5), Some(&book(0.48, 0.52))), "quote 0.48");
+    }
+
+    #[test]
+    fn crosses_once_edge_clears_the_threshold() {
+        let buy = order(Side::Buy, Outcome::Yes);
+        let snap = book(0.48, 0.52);
+        assert_eq!(place(&policy(0.0), &buy, &intent(0.50, 0.58, Urgency::Normal, 0.03), Some(&snap)), "cross 0.52");
+        // the taker fee counts against the edge: 200 bps of 0.48 leaves 0.0104, 300 bps 0.0056
+        assert_eq!(place(&policy(200.0), &buy, &intent(0.50, 0.58, Urgency::Normal, 0.04), Some(&snap)), "cross 0.52");
+        assert_eq!(place(&policy(300.0), &buy, &intent(0.50, 0.58, Urgency::Normal, 0.04), Some(&snap)), "quote 0.49");
+        // never past the max price
+        assert_eq!(place(&policy(0.0), &buy, &intent(0.50, 0.51, Urgency::Normal, 0.10), Some(&snap)), "quote 0.49");
+        // urgent takes any positive edge
+        assert_eq!(place(&policy(0.0), &buy, &intent(0.50, 0.58, Urgency::Urgent, 0.021), Some(&snap)), "cross 0.52");
+    }
+
+    #[test]
+    fn prices_round_away_from_the_touch() {
+        let p = policy(0.0);
+        let thin = |target| intent(target, target, Urgency::Normal, 0.0);
+        // blind quotes rest at the target, down to the tick for a buy and up for a sell
+        assert_eq!(place(&p, &order(Side::Buy, Outcome::Yes), &thin(0.5049), None), "quote 0.50");
+        assert_eq!(place(&p, &order(Side::Sell, Outcome::Yes), &thin(0.5049), None), "quote 0.51");
+        // f32 noise below a tick is not a price between ticks
+        assert_eq!(place(&p, &order(Side::Buy, Outcome::Yes), &thin(0.29), None), "quote 0.29");
+        assert_eq!(place(&p, &order(Side::Sell, Outcome::Yes), &thin(0.29), None), "quote 0.29");
+        // and a quote never leaves the price range
+        assert_eq!(place(&p, &order(Side::Buy, Outcome::Yes), &thin(0.001), None), "quote 0.01");
+    }
+
+    #[test]
+    fn no_outcome_prices_off_the_flipped_book() {
+        // YES 0.40 / 0.45 is NO 0.55 / 0.60
+        let snap = book(0.40, 0.45);
+        let buy_no = order(Side::Buy, Outcome::No);
+        assert_eq!(place(&policy(0.0), &buy_no, &intent(0.57, 0.65, Urgency::Normal, 0.02), Some(&snap)), "quote 0.56");
+        assert_eq!(place(&policy(0.0), &buy_no, &intent(0.57, 0.65, Urgency::Normal, 0.05), Some(&snap)), "cross 0.60");
+        let sell_no = order(Side::Sell, Outcome::No);
+        assert_eq!(place(&policy(0.0), &sell_no, &intent(0.57, 0.50, Urgency::Normal, 0.03), Some(&snap)), "cross 0.55");
+    }
+
+    #[test]
+    fn sells_mirror_buys() {
+        let sell = order(Side::Sell, Outcome::Yes);
+        let snap = book(0.48, 0.52);
+        assert_eq!(place(&policy(0.0), &sell, &intent(0.50, 0.42, Urgency::Normal, 0.02), Some(&snap)), "quote 0.51");
+        assert_eq!(place(&policy(0.0), &sell, &intent(0.50, 0.42, Urgency::Normal, 0.04), Some(&snap)), "cross 0.48");
+        // never below the least the sell takes
+        assert_eq!(place(&policy(0.0), &sell, &intent(0.50, 0.515, Urgency::Normal, 0.10), Some(&snap)), "quote 0.52");
+    }
+}
@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+use std::time::Instant;
+use tracing::info;
+use crate::config::config::SlicingCfg;
+use crate::core::types::{MarketDataSnap, Order, Outcome, Side};
+use crate::execution::policy::{ExecutionPolicy, Placement, Touch};
+
+/// Sizes below this are nothing (the CLOB trades in 0.01 contracts).
+const MIN_SIZE: f64 = 0.01;
+/// Prices within this are equal (book prices are f32).
+const PRICE_EPS: f64 = 1e-6;
+
+/// The child of an order currently at the venue.
+#[derive(Clone, Debug)]
+struct Child {
+    id: String,
+    price: f64,
+    size: f64,
+    filled: f64,
+    /// Resting at a price the policy chose, so it follows the book.
+    requotes: bool,
+}
+
+impl Child {
+    fn open(&self) -> f64 {
+        (self.size - self.filled).max(0.0)
+    }
+}
+
+/// An order as the strategy sent it, worked through one child at a time.
+#[derive(Clone, Debug)]
+struct Parent {
+    order: Order,
+    filled: f64,
+    active: Option<Child>,
+    next_child: u32,
+    last_sent: Option<Instant>,
+}
+
+impl Parent {
+    /// Fill-or-kill and immediate orders go out whole, once.
+    fn immediate(&self) -> bool {
+        self.order.is_immediate()
+    }
+
+    /// Size neither filled nor working in the active child.
+    fn unallocated(&self) -> f64 {
+        self.order.size as f64 - self.filled - self.active.as_ref().map_or(0.0, Child::open)
+    }
+}
+
+/// Splits orders into children capped by a fraction of the touch, prices each child with the
+/// execution policy, and re-prices resting children when the book moves. Children are named
+/// `<parent>/<n>`; their fills count toward the parent and whatever they leave unfilled goes
+/// back to the parent for the next child.
+pub struct Slicer {
+    cfg: SlicingCfg,
+    policy: Box<dyn ExecutionPolicy>,
+    parents: HashMap<String, Parent>,
+    /// Child id to parent id, for every child of a live parent.
+    children: HashMap<String, String>,
+}
+
+impl Slicer {
+    pub fn new(cfg: &SlicingCfg, policy: Box<dyn ExecutionPolicy>) -> Self {
+        Self { cfg: cfg.clone(), policy, parents: HashMap::new(), children: HashMap::new() }
+    }
+
+    pub fn add(&mut self, order: Order) {
+        let parent = Parent { order, filled: 0.0, active: None, next_child: 1, last_sent: None };
+        self.parents.insert(parent.order.client_order_id.clone(), parent);
+    }
+
+    /// Forgets a parent that reached a terminal state; returns its child still at the venue.
+    pub fn remove(&mut self, parent_id: &str) -> Option<String> {
+        let parent = self.parents.remove(parent_id)?;
+        self.children.retain(|_, p| p != parent_id);
+        parent.active.map(|c| c.id)
+    }
+
+    /// The parent's child currently at the venue.
+    pub fn active(&self, parent_id: &str) -> Option<&str> {
+        self.parents.get(parent_id)?.active.as_ref().map(|c| c.id.as_str())
+    }
+
+    pub fn parent(&self, child_id: &str) -> Option<&str> {
+        self.children.get(child_id).map(String::as_str)
+    }
+
+    /// Whether the parent of a child goes out whole, once.
+    pub fn immediate(&self, parent_id: &str) -> bool {
+        self.parents.get(parent_id).is_some_and(Parent::immediate)
+    }
+
+    /// Live parents, in `market_id` if given.
+    pub fn parents(&self, market_id: Option<&str>) -> Vec<(String, String)> {
+        let mut ids: Vec<(String, String)> = self
+            .parents
+            .values()
+            .filter(|p| market_id.is_none_or(|m| p.order.market_id == m))
+            .map(|p| (p.order.client_order_id.clone(), p.order.market_id.clone()))
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Counts a child fill toward its parent.
+    pub fn fill(&mut self, child_id: &str, size: f64) {
+        let Some(parent) = self.children.get(child_id).and_then(|p| self.parents.get_mut(p)) else { return };
+        parent.filled += size;
+        if let Some(child) = parent.active.as_mut().filter(|c| c.id == child_id) {
+            child.filled += size;
+            if child.open() < MIN_SIZE {
+                parent.active = None;
+            }
+        }
+    }
+
+    /// The child is no longer working (cancelled, rejected). `false` if it was not the
+    /// active one, e.g. it was already replaced.
+    pub fn finish(&mut self, child_id: &str) -> bool {
+        let Some(parent) = self.children.get(child_id).and_then(|p| self.parents.get_mut(p)) else { return false };
+        if parent.active.as_ref().is_some_and(|c| c.id == child_id) {
+            parent.active = None;
+            return true;
+        }
+        false
+    }
+
+    /// Next child for a parent with nothing working, rate limited after the first.
+    pub fn next(&mut self, parent_id: &str, book: Option<&MarketDataSnap>, now: Instant) -> Option<Order> {
+        let parent = self.parents.get(parent_id)?;
+        if parent.active.is_some() || !self.may_send(parent, now) {
+            return None;
+        }
+        if parent.immediate() && parent.next_child > 1 {
+            return None;
+        }
+        self.spawn(parent_id, book, now)
+    }
+
+    /// The parent's resting child if the book has moved its price. The caller cancels it and,
+    /// once the venue confirms and the fills that raced the cancel are counted, asks
+    /// `replace` for its successor; until then the child stays active. Counts as a send for
+    /// the rate limit, so a cancel that keeps failing is not retried on every book.
+    pub fn requote(&mut self, parent_id: &str, book: &MarketDataSnap, now: Instant) -> Option<String> {
+        let parent = self.parents.get(parent_id)?;
+        let child = parent.active.as_ref().filter(|c| c.requotes)?;
+        if !self.may_send(parent, now) {
+            return None;
+        }
+        let intent = parent.order.intent.as_ref()?;
+        // while the child is still the best bid (offer) the touch is our own quote; pricing off
+        // it would only outbid ourselves, so wait for the touch to move or someone to improve on it
+        let at_touch = Touch::of(book, parent.order.outcome).is_some_and(|t| {
+            let own = if parent.order.side == Side::Buy { t.bid } else { t.ask };
+            (own - child.price).abs() <= PRICE_EPS
+        });
+        let moved = match self.policy.place(&parent.order, intent, Some(book)) {
+            Placement::Cross { .. } => true,
+            Placement::Quote { .. } if at_touch => false,
+            Placement::Quote { price } => (price - child.price).abs() > PRICE_EPS,
+        };
+        if !moved {
+            return None;
+        }
+        let id = child.id.clone();
+        self.parents.get_mut(parent_id)?.last_sent = Some(now);
+        Some(id)
+    }
+
+    /// The child taking over from `old` after the venue confirmed its cancel, sized from what
+    /// is still unallocated.
+    pub fn replace(&mut self, parent_id: &str, old: &str, book: &MarketDataSnap, now: Instant) -> Option<Order> {
+        self.finish(old);
+        self.spawn(parent_id, Some(book), now)
+    }
+
+    fn may_send(&self, parent: &Parent, now: Instant) -> bool {
+        parent.last_sent.is_none_or(|last| now.duration_since(last) >= self.cfg.replace_interval)
+    }
+
+    /// Prices and sizes a child of the unallocated size and makes it the active one.
+    fn spawn(&mut self, parent_id: &str, book: Option<&MarketDataSnap>, now: Instant) -> Option<Order> {
+        let parent = self.parents.get(parent_id)?;
+        let order = &parent.order;
+        let unallocated = parent.unallocated();
+        if unallocated < MIN_SIZE {
+            return None;
+        }
+        let (price, post_only, placement) = match &order.intent {
+            Some(intent) => match self.policy.place(order, intent, book) {
+                p @ Placement::Cross { price } => (price, false, Some(p)),
+                p @ Placement::Quote { price } => (price, true, Some(p)),
+            },
+            None => (order.price as f64, order.post_only, None),
+        };
+        let crossing = matches!(placement, Some(Placement::Cross { .. }));
+        let requotes = post_only && order.intent.is_some();
+        let size = match book.and_then(|b| depth(b, order.side, order.outcome, crossing || placement.is_none())) {
+            Some(depth) if !parent.immediate() => {
+                (depth * self.cfg.max_depth_fraction).max(self.cfg.min_child_size).min(unallocated)
+            }
+            _ => unallocated,
+        };
+        let size = (size / MIN_SIZE).floor() * MIN_SIZE;
+        if size < MIN_SIZE {
+            return None;
+        }
+
+        let id = format!("{parent_id}/{}", parent.next_child);
+        let child = Order {
+            client_order_id: id.clone(),
+            price: price as f32,
+            size: size as f32,
+            post_only,
+            intent: None,
+            ..order.clone()
+        };
+        info!(
+            parent = %parent_id,
+            child = %id,
+            placement = ?placement,
+            price,
+            size,
+            unallocated,
+            "child order"
+        );
+        let parent = self.parents.get_mut(parent_id)?;
+        parent.next_child += 1;
+        parent.last_sent = Some(now);
+        parent.active = Some(Child { id: id.clone(), price, size, filled: 0.0, requotes });
+        self.children.insert(id, parent_id.to_string());
+        Some(child)
+    }
+}
+
+/// Contracts at the touch an order takes from (`take`) or joins.
+fn depth(snap: &MarketDataSnap, side: Side, outcome: Outcome, take: bool) -> Option<f64> {
+    // buying NO is selling YES, so it takes the YES bid and joins the YES ask
+    let takes_ask = (side == Side::Buy) == (outcome == Outcome::Yes);
+    let size = (if takes_ask == take { snap.ask_size } else { snap.bid_size }) as f64;
+    (size > 0.0).then_some(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::*;
+    use crate::config::config::{FeesCfg, PolicyCfg};
+    use crate::core::types::{OrderIntent, OrderType, Side, TimeInForce, Urgency};
+    use crate::execution::policy::LimitFirst;
+
+    /// Children as large as the touch, so their size is what the parent has unallocated.
+    fn slicer() -> Slicer {
+        let cfg = SlicingCfg { max_depth_fraction: 1.0, min_child_size: 5.0, replace_interval: Duration::from_secs(1) };
+        let policy = LimitFirst::new(&PolicyCfg { cross_threshold: 0.01, tick: 0.01 }, &FeesCfg::default());
+        Slicer::new(&cfg, Box::new(policy))
+    }
+
+    /// Buys 40 YES with too little edge to cross, so it rests a tick inside the spread.
+    fn order() -> Order {
+        Order {
+            client_order_id: "P".into(),
+            market_id: "m".into(),
+            side: Side::Buy,
+            outcome: Outcome::Yes,
+            token_id: "1".into(),
+            order_type: OrderType::Limit,
+            time_in_force: TimeInForce::Gtd,
+            ttl: None,
+            post_only: false,
+            neg_risk: false,
+            price: 0.52,
+            size: 40.0,
+            intent: Some(OrderIntent { target_price: 0.5, max_price: 0.58, urgency: Urgency::Normal, edge: 0.02 }),
+        }
+    }
+
+    fn book(bid: f32, ask: f32) -> MarketDataSnap {
+        MarketDataSnap { market_id: "m".into(), book_ts_ms: 0, best_bid: bid, best_ask: ask, bid_size: 100.0, ask_size: 100.0 }
+    }
+
+    /// A slicer with `P/1` resting 40 at 0.49.
+    fn resting(t0: Instant) -> Slicer {
+        let mut s = slicer();
+        s.add(order());
+        let child = s.next("P", Some(&book(0.48, 0.52)), t0).unwrap();
+        assert_eq!((child.client_order_id.as_str(), child.price, child.size), ("P/1", 0.49, 40.0));
+        s
+    }
+
+    #[test]
+    fn requote_waits_for_the_cancel() {
+        let t0 = Instant::now();
+        let t1 = t0 + Duration::from_secs(2);
+        let mut s = resting(t0);
+        let moved = book(0.50, 0.53);
+        assert_eq!(s.requote("P", &moved, t1).as_deref(), Some("P/1"));
+        // still working until the venue confirms the cancel
+        assert_eq!(s.active("P"), Some("P/1"));
+        assert!(s.next("P", Some(&moved), t1 + Duration::from_secs(2)).is_none());
+
+        // 8 filled before the cancel landed; the replacement covers only the rest
+        s.fill("P/1", 8.0);
+        let child = s.replace("P", "P/1", &moved, t1).unwrap();
+        assert_eq!((child.client_order_id.as_str(), child.price, child.size), ("P/2", 0.51, 32.0));
+        assert_eq!(s.active("P"), Some("P/2"));
+    }
+
+    #[test]
+    fn failed_cancel_keeps_the_child() {
+        let t0 = Instant::now();
+        let t1 = t0 + Duration::from_secs(2);
+        let mut s = resting(t0);
+        let moved = book(0.50, 0.53);
+        assert!(s.requote("P", &moved, t1).is_some());
+        // the cancel failed: nothing else goes out for the parent, and no retry before the interval
+        assert_eq!(s.active("P"), Some("P/1"));
+        assert!(s.next("P", Some(&moved), t1).is_none());
+        assert!(s.requote("P", &moved, t1 + Duration::from_millis(500)).is_none());
+        assert_eq!(s.requote("P", &moved, t1 + Duration::from_secs(1)).as_deref(), Some("P/1"));
+    }
+
+    #[test]
+    fn raced_fill_completing_the_child() {
+        let t0 = Instant::now();
+        let mut s = resting(t0);
+        let moved = book(0.50, 0.53);
+        let t1 = t0 + Duration::from_secs(2);
+        assert!(s.requote("P", &moved, t1).is_some());
+        s.fill("P/1", 40.0);
+        assert!(s.replace("P", "P/1", &moved, t1).is_none());
+    }
+
+    #[test]
+    fn no_requote_against_own_quote() {
+        let t0 = Instant::now();
+        let mut s = resting(t0);
+        // the best bid is our 0.49: quoting a tick above it would outbid ourselves
+        assert!(s.requote("P", &book(0.49, 0.53), t0 + Duration::from_secs(2)).is_none());
+        // someone else improved on it
+        assert!(s.requote("P", &book(0.50, 0.53), t0 + Duration::from_secs(2)).is_some());
+    }
+}
@@ -0,0 +1,40 @@
+use crate::core::types::{Liquidity, Outcome, Side};
+
+/// What a venue reports back about an order; the order manager turns these into status
+/// updates and executions.
+#[derive(Clone, Debug)]
+pub enum VenueEvent {
+    Acknowledged { client_order_id: String, venue_order_id: String },
+    Fill { client_order_id: String, price: f64, size: f64, fee: f64, liquidity: Liquidity },
+    Cancelled { client_order_id: String, reason: String },
+    Rejected { client_order_id: String, reason: String },
+}
+
+/// An order resting at a venue, as the venue sees it.
+#[derive(Clone, Debug)]
+pub struct VenueOrder {
+    pub client_order_id: String,
+    pub venue_order_id: String,
+    pub market_id: String,
+    pub side: Side,
+    pub outcome: Outcome,
+    pub price: f64,
+    pub remaining: f64,
+}
+
+/// Outcome tokens held at a venue.
+#[derive(Clone, Debug)]
+pub struct Position {
+    pub token_id: String,
+    pub market_id: String,
+    pub outcome: Outcome,
+    pub size: f64,
+}
+
+/// USDC at a venue.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Balances {
+    pub cash: f64,
+    /// Committed to open buy orders.
+    pub reserved: f64,
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+use anyhow::{Context, Result};
+use reqwest::Client;
+use crate::config::config::{ClobCfg, ExecutionCfg, ExecutionMode};
+use crate::core::types::{MarketDataSnap, Order, Side};
+use crate::execution::clob::ClobVenue;
+use crate::execution::paper::PaperVenue;
+use crate::execution::types::{Balances, Position, VenueEvent, VenueOrder};
+
+/// Where orders go. ExecutionActor drives a venue through this and never looks behind it,
+/// so paper, mock and live trading differ only in `execution.mode`.
+#[async_trait::async_trait]
+pub trait Venue: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Submits an order; the events cover whatever happened on arrival.
+    async fn place(&mut self, order: &Order) -> Vec<VenueEvent>;
+
+    /// Pulls a resting order; the events are fills it got before the cancel landed. Fails if
+    /// the venue no longer has it open or did not confirm the cancel.
+    async fn cancel(&mut self, client_order_id: &str) -> Result<Vec<VenueEvent>>;
+
+    fn open_orders(&self) -> Vec<VenueOrder>;
+
+    async fn positions(&self) -> Result<Vec<Position>>;
+
+    async fn balances(&self) -> Result<Balances>;
+
+    /// Latest book of a market, for venues that match locally.
+    async fn on_book(&mut self, _snap: &MarketDataSnap) -> Vec<VenueEvent> {
+        Vec::new()
+    }
+
+    /// Fills and cancellations of resting orders since the last poll, for venues that
+    /// report asynchronously.
+    async fn poll(&mut self) -> Vec<VenueEvent> {
+        Vec::new()
+    }
+
+    /// How often `poll` should run; `None` if it never needs to.
+    fn poll_interval(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// The venue `execution.mode` selects.
+pub fn from_config(http: Client, cfg: &ExecutionCfg) -> Result<Box<dyn Venue>> {
+    Ok(match cfg.mode {
+        ExecutionMode::Paper => Box::new(PaperVenue::new(&cfg.fees, &cfg.paper)),
+        ExecutionMode::Mock => {
+            let clob = cfg.clob.as_ref().context("execution.clob missing")?;
+            let mock = ClobCfg { url: cfg.mock_url.clone(), ..clob.clone() };
+            Box::new(ClobVenue::new(http, &mock, &cfg.fees)?)
+        }
+        ExecutionMode::Clob => {
+            let clob = cfg.clob.as_ref().context("execution.clob missing")?;
+            Box::new(ClobVenue::new(http, clob, &cfg.fees)?)
+        }
+    })
+}
+
+/// USDC committed to open buys.
+pub fn reserved(open: &[VenueOrder]) -> f64 {
+    open.iter().filter(|o| o.side == Side::Buy).fold(0.0, |sum, o| sum + o.price * o.remaining)
+}
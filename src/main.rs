@@ -7,6 +7,9 @@ mod rss;
 mod finjuice;
 mod core;
 mod config;
+mod text;
+mod enrichment;
+mod tools;
 
 use std::time::Duration;
 use anyhow::Result;
@@ -15,18 +18,33 @@ use tracing::{error, info, info_span, Instrument};
 use tokio_util::sync::CancellationToken;
 use bus::types::Bus;
 use execution::actor::ExecutionActor;
+use execution::policy::LimitFirst;
+use execution::slicer::Slicer;
 use finjuice::actor::FinJuiceActor;
 use marketdata::actor::MarketDataActor;
 use strategy::actor::StrategyActor;
+use strategy::journal::DecisionLog;
+use strategy::probability::LogisticCalibrator;
 use config::config::AppCfg;
 use core::types::Actor;
+use enrichment::actor::EnrichmentActor;
 use polymarket::actor::PolyActor;
 use rss::actor::RssActor;
+use text::dates::DateExtractor;
+use text::entities::EntityExtractor;
 
 #[tokio::main]
 async fn main()  -> Result<()>   {
     tracing_subscriber::fmt::init();
 
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("calibrate") {
+        return tools::calibrate::run(&args[2..]);
+    }
+    if args.get(1).map(String::as_str) == Some("mock-clob") {
+        return tools::mock_clob::run(&args[2..]).await;
+    }
+
     let cfg = AppCfg::load("config.yml")?;
 
     // Root span for the supervisor/main thread
@@ -60,8 +78,17 @@ async fn main()  -> Result<()>   {
     let rss  = RssActor::new(bus.clone(), client.clone(), cfg.rss.clone(), shutdown.clone());
     let fj   = FinJuiceActor::new(bus.clone(), client.clone(), cfg.financial_juice.clone(), shutdown.clone());
     let market_data = MarketDataActor::new(bus.clone(), shutdown.clone());
-    let strat = StrategyActor::new(bus.clone(), shutdown.clone());
-    let exec = ExecutionActor::new(bus.clone(), shutdown.clone());
+    let entities = EntityExtractor::load(&cfg.text.entities)?;
+    let dates = DateExtractor::new(&cfg.text.timezone)?;
+    let enrichment = EnrichmentActor::new(bus.clone(), cfg.text.clone(), entities, dates, shutdown.clone());
+    let market_entities = EntityExtractor::load(&cfg.text.entities)?;
+    let calibrator = Box::new(LogisticCalibrator::new(&cfg.strategy.probability));
+    let strat = StrategyActor::new(bus.clone(), cfg.strategy.clone(), market_entities, calibrator, shutdown.clone());
+    let decision_log = DecisionLog::new(bus.clone(), cfg.strategy.decision_log.clone(), shutdown.clone());
+    let venue = execution::venue::from_config(client.clone(), &cfg.execution)?;
+    let policy = Box::new(LimitFirst::new(&cfg.execution.policy, &cfg.execution.fees));
+    let slicer = Slicer::new(&cfg.execution.slicing, policy);
+    let exec = ExecutionActor::new(bus.clone(), venue, slicer, shutdown.clone());
 
     info!("Spawning actors");
     let mut actors = tokio::task::JoinSet::new();
@@ -69,8 +96,10 @@ async fn main()  -> Result<()>   {
     actors.spawn(poly.run().instrument(info_span!("PolyMarket")));
     actors.spawn(rss.run().instrument(info_span!("RSS")));
     actors.spawn(fj.run().instrument(info_span!("FinancialJuice")));
+    actors.spawn(enrichment.run().instrument(info_span!("Enrichment")));
     actors.spawn(market_data.run().instrument(info_span!("MarketData")));
     actors.spawn(strat.run().instrument(info_span!("Strat")));
+    actors.spawn(decision_log.run().instrument(info_span!("DecisionLog")));
     actors.spawn(exec.run().instrument(info_span!("Exec")));
 
     info!("Waiting for actors");
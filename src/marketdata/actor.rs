@@ -36,8 +36,8 @@ impl Actor for MarketDataActor {
                         Ok(req) => {
                             // TODO: fetch data using *req Arc<MarketDataRequest>
                             let snap = MarketDataSnap {
-                                market_id: "".to_string(),
-                                book_ts_ms: 0,
+                                market_id: req.market_id.clone(),
+                                book_ts_ms: chrono::Utc::now().timestamp_millis(),
                                 best_bid: 0.0,
                                 best_ask: 0.0,
                                 bid_size: 0.0,
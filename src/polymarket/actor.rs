@@ -3,25 +3,47 @@ use crate::bus::types::Bus;
 use crate::core::types::{Actor, PolyMarketEvent};
 use anyhow::Result;
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use reqwest::Client;
 use futures::{stream, StreamExt};
 use tokio::time::interval;
 use crate::config::config::PolyCfg;
+use crate::core::types::MarketChange;
+use crate::polymarket::catalogue::Catalogue;
 
 pub struct PolyActor {
     pub bus: Bus,
     pub client: Client,
     pub poly_cfg: PolyCfg,
+    pub catalogue: Catalogue,
     pub shutdown: CancellationToken
 }
 
 impl PolyActor {
     pub fn new(bus: Bus, client: Client, poly_cfg: PolyCfg, shutdown: CancellationToken) -> PolyActor {
-        Self { bus, client, poly_cfg, shutdown }
+        Self { bus, client, poly_cfg, catalogue: Catalogue::default(), shutdown }
     }
 
-    async fn fetch_events_page(&self, offset: u32) -> Result<Vec<PolyMarketEvent>> {
+    async fn publish_changes(&mut self, events: &[PolyMarketEvent]) {
+        let changes = self.catalogue.diff(events);
+        let count = |f: fn(&MarketChange) -> bool| changes.iter().filter(|c| f(c)).count();
+        info!(
+            markets = self.catalogue.len(),
+            added = count(|c| matches!(c, MarketChange::Added(_))),
+            updated = count(|c| matches!(c, MarketChange::Updated { .. })),
+            removed = count(|c| matches!(c, MarketChange::Removed { .. })),
+            closed = count(|c| matches!(c, MarketChange::Closed { .. })),
+            "catalogue refreshed"
+        );
+        for change in changes {
+            if let Err(e) = self.bus.market_changes.publish(change).await {
+                error!(?e, "publish to market_changes failed");
+            }
+        }
+    }
+
+    /// Events on one page, and how many rows the page had before malformed ones were skipped.
+    async fn fetch_events_page(&self, offset: u32) -> Result<(Vec<PolyMarketEvent>, usize)> {
         let res = self.client
             .get(self.poly_cfg.gammaUrl.clone())
             .query(&[
@@ -34,9 +56,18 @@ impl PolyActor {
             .send()
             .await?
             .error_for_status()?
-            .json::<Vec<PolyMarketEvent>>()
+            .json::<Vec<serde_json::Value>>()
             .await?;
-        Ok(res)
+        let rows = res.len();
+        // one malformed event should not cost the rest of the page
+        let events = res
+            .into_iter()
+            .filter_map(|row| {
+                let id = row.get("id").cloned();
+                serde_json::from_value(row).inspect_err(|e| warn!(?id, error = %e, "skipping malformed event")).ok()
+            })
+            .collect();
+        Ok((events, rows))
     }
 
     async fn fetch_all_active_polymarket_events(&self) -> Result<Vec<PolyMarketEvent>> {
@@ -44,13 +75,13 @@ impl PolyActor {
         let mut offset = 0;
 
         loop {
-            let page = self.fetch_events_page(offset).await?;
+            let (page, len) = self.fetch_events_page(offset).await?;
 
-            if page.is_empty() { break; }
-            let len = page.len();
+            if len == 0 { break; }
             for ev in page {
                 rows.push(ev);
             }
+            // a short page is the last; skipped rows still count, or they would end it early
             if len < self.poly_cfg.pageLimit as usize { break; }
             offset += self.poly_cfg.pageLimit;
         }
@@ -65,6 +96,7 @@ impl Actor for PolyActor {
 
         // throttle the loop
         let mut tick = interval(Duration::from_secs(self.poly_cfg.marketListRefresh.as_secs())); // refresh cadence
+        let mut snapshot_rx = self.bus.market_snapshot_request.subscribe();
 
         loop {
             tokio::select! {
@@ -74,10 +106,29 @@ impl Actor for PolyActor {
                     break;
                 }
 
+                // A consumer lost diffs; resend the whole catalogue
+                res = snapshot_rx.recv() => {
+                    match res {
+                        Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
+                            let markets = self.catalogue.snapshot();
+                            info!(markets = markets.len(), "publishing market snapshot");
+                            if let Err(e) = self.bus.market_changes.publish(MarketChange::Snapshot(markets)).await {
+                                error!(?e, "publish to market_changes failed");
+                            }
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                            error!("market_snapshot_request stream closed; exiting PolyActor");
+                            break;
+                        }
+                    }
+                }
+
                 //Fetch active polymarket events and markets
                 _ = tick.tick() => {
                      match self.fetch_all_active_polymarket_events().await  {
                         Ok(poly_events) => {
+                            self.publish_changes(&poly_events).await;
+
                             let bus = self.bus.clone();
                             let publish_futs = poly_events.into_iter().map(
                                 move |ev| {
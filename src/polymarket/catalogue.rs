@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use crate::core::types::{IndexedMarket, MarketChange, MarketField, PolyMarketEvent};
+
+/// Last full listing of tradable markets. Each refresh is diffed against it so downstream
+/// consumers only see what changed instead of the whole universe every `marketListRefresh`.
+#[derive(Default)]
+pub struct Catalogue {
+    markets: HashMap<String, IndexedMarket>,
+}
+
+impl Catalogue {
+    pub fn len(&self) -> usize {
+        self.markets.len()
+    }
+
+    /// Every market in the last listing.
+    pub fn snapshot(&self) -> Vec<IndexedMarket> {
+        self.markets.values().cloned().collect()
+    }
+
+    /// `events` must be a complete listing: markets missing from it are reported as removed.
+    pub fn diff(&mut self, events: &[PolyMarketEvent]) -> Vec<MarketChange> {
+        let mut changes = Vec::new();
+        let mut next = HashMap::with_capacity(self.markets.len());
+
+        for m in events.iter().flat_map(IndexedMarket::from_event) {
+            let id = m.id().to_string();
+            let previous = self.markets.remove(&id);
+            if m.market.closed || m.market.archived || !m.market.active {
+                if previous.is_some() {
+                    changes.push(MarketChange::Closed { market_id: id });
+                }
+                continue;
+            }
+            match previous {
+                None => changes.push(MarketChange::Added(m.clone())),
+                Some(old) => {
+                    let changed = changed_fields(&old, &m);
+                    if !changed.is_empty() {
+                        changes.push(MarketChange::Updated { market: m.clone(), changed });
+                    }
+                }
+            }
+            next.insert(id, m);
+        }
+
+        changes.extend(self.markets.drain().map(|(market_id, _)| MarketChange::Removed { market_id }));
+        self.markets = next;
+        changes
+    }
+}
+
+fn changed_fields(old: &IndexedMarket, new: &IndexedMarket) -> Vec<MarketField> {
+    let (o, n) = (&old.market, &new.market);
+    let checks = [
+        (MarketField::Question, o.question != n.question),
+        (MarketField::Description, o.description != n.description),
+        (MarketField::EventTitle, old.event_title != new.event_title),
+        (MarketField::Tags, old.tags != new.tags),
+        (MarketField::Category, old.category != new.category),
+        (MarketField::Outcomes, o.outcomes != n.outcomes || o.clob_token_ids != n.clob_token_ids),
+        (MarketField::EndDate, old.end_date != new.end_date),
+        (MarketField::Prices, o.outcome_prices != n.outcome_prices || o.last_trade_price != n.last_trade_price),
+        (MarketField::Quote, o.best_bid != n.best_bid || o.best_ask != n.best_ask || o.spread != n.spread),
+        (MarketField::Liquidity, o.liquidity != n.liquidity),
+        (MarketField::Volume, o.volume != n.volume || o.volume_24hr != n.volume_24hr),
+        (MarketField::OpenInterest, old.open_interest != new.open_interest),
+        (MarketField::AcceptingOrders, o.accepting_orders != n.accepting_orders),
+        (MarketField::TickSize, o.order_price_min_tick_size != n.order_price_min_tick_size || o.order_min_size != n.order_min_size),
+    ];
+    checks.into_iter().filter(|(_, changed)| *changed).map(|(f, _)| f).collect()
+}
@@ -1,31 +1,514 @@
+use std::collections::HashMap;
 use std::net::Shutdown;
+use std::time::Instant;
 use crate::bus::types::Bus;
-use crate::core::types::{Actor, Execution, MarketDataSnap, Order, RawNews};
+use crate::config::config::StrategyCfg;
+use crate::core::types::{
+    Actor, EnrichedNews, Execution, IndexedMarket, MarketChange, MarketDataRequest, MarketDataSnap, MarketSnapshotRequest,
+    Order, OrderType, Outcome, Side, TimeInForce,
+};
+use crate::strategy::edge::{EdgeRule, NoTradeReason, Quote, Trade};
+use crate::strategy::filters::{Dropped, FilterChain};
+use crate::strategy::index::MarketIndex;
+use crate::strategy::probability::Calibrator;
+use crate::strategy::scorer::Scorer;
+use crate::strategy::select::Selector;
+use crate::strategy::sizing::{self, Exposure, SizingInput};
+use crate::strategy::types::{Candidate, CandidateRecord, Decision, DropRecord, MarketDecision, OpenDecision, PendingSignal};
+use crate::text::entities::EntityExtractor;
+use crate::text::normalizer::{NewsTokens, Normalizer};
 use anyhow::Result;
+use chrono::Utc;
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
 pub struct StrategyActor {
     pub bus: Bus,
+    pub cfg: StrategyCfg,
+    /// Tradable markets, kept current from `market_changes`.
+    pub index: MarketIndex,
+    pub filters: FilterChain,
+    pub scorer: Scorer,
+    pub selector: Selector,
+    pub calibrator: Box<dyn Calibrator>,
+    pub edge: EdgeRule,
+    /// Signals waiting for a quote, by market id.
+    pub pending: HashMap<String, Vec<PendingSignal>>,
+    /// Filled notional, for the sizing caps.
+    pub exposure: Exposure,
+    /// Contracts bought less contracts sold, by market and outcome.
+    pub holdings: HashMap<(String, Outcome), f64>,
+    /// Orders published but not yet in `open_orders` or `executions`, with when they were
+    /// sent, by client order id.
+    pub in_flight: HashMap<String, (Order, Instant)>,
+    pub next_decision_id: u64,
+    /// Decisions waiting on quotes, by id.
+    pub open_decisions: HashMap<u64, OpenDecision>,
+    /// Finished decisions not yet published.
+    pub decisions: Vec<Decision>,
+    pub normalizer: Normalizer,
+    /// Tags market text when it is indexed, for the entity-overlap filter.
+    pub entities: EntityExtractor,
+    /// Lagged on `market_changes` and waiting for the snapshot that rebuilds the index.
+    pub resync_requested: bool,
     pub shutdown: CancellationToken
 }
 
 impl StrategyActor {
-    pub fn new(bus: Bus, shutdown: CancellationToken) -> StrategyActor {
-        Self { bus, shutdown }
+    pub fn new(
+        bus: Bus,
+        cfg: StrategyCfg,
+        entities: EntityExtractor,
+        calibrator: Box<dyn Calibrator>,
+        shutdown: CancellationToken,
+    ) -> StrategyActor {
+        let index = MarketIndex::new(&cfg.index);
+        let filters = FilterChain::new(&cfg.filters);
+        let scorer = Scorer::new(&cfg.scorer);
+        let selector = Selector::new(&cfg.select);
+        let edge = EdgeRule::new(&cfg.trade);
+        Self {
+            bus,
+            cfg,
+            index,
+            filters,
+            scorer,
+            selector,
+            calibrator,
+            edge,
+            pending: HashMap::new(),
+            exposure: Exposure::default(),
+            holdings: HashMap::new(),
+            in_flight: HashMap::new(),
+            next_decision_id: 0,
+            open_decisions: HashMap::new(),
+            decisions: Vec::new(),
+            normalizer: Normalizer::default(),
+            entities,
+            resync_requested: false,
+            shutdown,
+        }
     }
 
-    fn decide_from_tick(&self, snap: &MarketDataSnap) -> Option<Order> {
-        // TODO: real logic
-        None
+    fn tag(&self, mut market: IndexedMarket) -> IndexedMarket {
+        let mut title = self.normalizer.tokenize(market.title());
+        if let Some(event_title) = &market.event_title {
+            title.extend(self.normalizer.tokenize(event_title));
+        }
+        let description = self.normalizer.tokenize(market.market.description.as_deref().unwrap_or_default());
+        market.entities = self.entities.extract(&NewsTokens { title, description });
+        market
     }
 
-    fn decide_from_news(&self, news: &RawNews) -> Option<Order> {
-        // TODO: real logic
-        None
+    /// Re-reads the entity dictionaries and, if they changed, re-tags every indexed market so
+    /// market entities stay comparable with the news side.
+    fn reload_entities(&mut self) {
+        match self.entities.reload() {
+            Ok(true) => {
+                let retagged: Vec<IndexedMarket> = self.index.markets().map(|m| self.tag(m.clone())).collect();
+                let markets = retagged.len();
+                for m in retagged {
+                    self.index.update(m);
+                }
+                info!(markets, "StrategyActor: entity dictionaries reloaded, markets re-tagged");
+            }
+            Ok(false) => {}
+            Err(e) => warn!(?e, "StrategyActor: entity dictionary reload failed, keeping previous"),
+        }
+    }
+
+    fn on_market_change(&mut self, change: &MarketChange) {
+        match change {
+            MarketChange::Added(m) => self.index.insert(self.tag(m.clone())),
+            MarketChange::Updated { market, changed } => {
+                let previous = self.index.get(market.id()).map(|m| m.entities.clone());
+                match previous {
+                    Some(entities) if !changed.iter().any(|f| f.is_indexed()) => {
+                        self.index.update(IndexedMarket { entities, ..market.clone() });
+                    }
+                    // text changed, or we missed the Added while lagging
+                    _ => self.index.insert(self.tag(market.clone())),
+                }
+            }
+            MarketChange::Removed { market_id } | MarketChange::Closed { market_id } => {
+                self.index.remove(market_id);
+            }
+            MarketChange::Snapshot(markets) => {
+                let mut index = MarketIndex::new(&self.cfg.index);
+                for m in markets {
+                    index.insert(self.tag(m.clone()));
+                }
+                info!(before = self.index.len(), after = index.len(), "market index rebuilt from snapshot");
+                self.index = index;
+                self.resync_requested = false;
+            }
+        }
+    }
+
+    /// BM25 candidates for the headline that pass the hard filters, best first, and those
+    /// that didn't.
+    fn candidates(&self, news: &EnrichedNews) -> (Vec<Candidate<'_>>, Vec<Dropped<'_>>) {
+        if self.index.is_empty() {
+            return (Vec::new(), Vec::new());
+        }
+        let started = Instant::now();
+        let hits = self.index.search(news.tokens.iter(), self.cfg.index.candidates);
+        debug!(
+            hits = hits.len(),
+            elapsed_us = started.elapsed().as_micros() as u64,
+            top = ?hits.iter().take(5).filter_map(|(id, s)| self.index.get(id).map(|m| (m.title(), *s))).collect::<Vec<_>>(),
+            "bm25 candidates"
+        );
+
+        let retrieved: Vec<Candidate> = hits
+            .iter()
+            .filter_map(|(id, bm25)| self.index.get(id).map(|market| Candidate { market, bm25: *bm25 }))
+            .collect();
+        let (kept, dropped) = self.filters.apply(news, retrieved);
+        for d in &dropped {
+            debug!(
+                market = d.candidate.market.id(),
+                title = d.candidate.market.title(),
+                bm25 = d.candidate.bm25,
+                filter = d.filter,
+                reason = %d.reason,
+                "candidate dropped"
+            );
+        }
+        info!(retrieved = hits.len(), kept = kept.len(), dropped = dropped.len(), title = %news.raw.title, "candidates filtered");
+        (kept, dropped)
+    }
+
+    /// Settles every signal waiting on this market's quote; orders for those with enough edge.
+    fn decide_from_tick(&mut self, snap: &MarketDataSnap) -> Vec<Order> {
+        let Some(signals) = self.pending.remove(&snap.market_id) else { return Vec::new() };
+        self.prune_in_flight();
+        let mut orders = Vec::new();
+        for signal in signals {
+            let record = self.evaluate(&signal, snap);
+            if let Ok(order) = &record.result {
+                // counts toward exposure until the ExecutionActor has registered it
+                self.in_flight.insert(order.client_order_id.clone(), (order.clone(), Instant::now()));
+                orders.push(order.clone());
+            }
+            self.settle(signal.decision_id, record);
+        }
+        orders
     }
 
-    fn decide_from_executions(&self, news: &Execution) -> Option<Order> {
+    /// Edge, then size, for one signal against its quote.
+    fn evaluate(&self, signal: &PendingSignal, snap: &MarketDataSnap) -> MarketDecision {
+        let market = &signal.market;
+        let mut record = MarketDecision::new(signal, NoTradeReason::NoQuote);
+        let check = match self.edge.evaluate(signal.belief, snap) {
+            Ok(check) => check,
+            Err(reason) => {
+                info!(market = market.id(), title = %signal.title, reason = %reason, "no trade");
+                record.result = Err(reason);
+                return record;
+            }
+        };
+        record.quote = Some(check.quote);
+        record.edge = Some(check.edge);
+        record.threshold = Some(check.threshold);
+        let trade = match check.decision {
+            Ok(trade) => trade,
+            Err(reason) => {
+                info!(market = market.id(), title = %signal.title, belief = signal.belief, mid = check.quote.mid(), reason = %reason, "no trade");
+                record.result = Err(reason);
+                return record;
+            }
+        };
+        // a view against an outcome we hold sells it, rather than buying the other one on top
+        let exit = trade.sell_other();
+        let held = self.unsold(market.id(), exit.outcome);
+        if held >= 1.0
+            && let Some(token_id) = market.token_id(exit.outcome)
+        {
+            info!(
+                market = market.id(),
+                title = %signal.title,
+                belief = signal.belief,
+                mid = check.quote.mid(),
+                edge = check.edge,
+                outcome = ?exit.outcome,
+                price = exit.price,
+                contracts = held,
+                "exit"
+            );
+            record.result = Ok(self.order(signal, &check.quote, &exit, token_id, held));
+            return record;
+        }
+        let Some(token_id) = market.token_id(trade.outcome) else {
+            info!(market = market.id(), title = %signal.title, outcome = ?trade.outcome, reason = %NoTradeReason::NoToken, "no trade");
+            record.result = Err(NoTradeReason::NoToken);
+            return record;
+        };
+        let exposure = self.committed();
+        let size = sizing::size(&self.cfg.sizing, &SizingInput {
+            belief: signal.belief,
+            outcome: trade.outcome,
+            price: trade.price,
+            depth: trade.depth,
+            market_exposure: exposure.market(market.id()),
+            event_exposure: exposure.event(&market.event_id),
+            gross_exposure: exposure.gross(),
+        });
+        record.size = Some(size);
+        if size.contracts < 1.0 {
+            let reason = NoTradeReason::ZeroSize { cap: size.cap };
+            info!(market = market.id(), title = %signal.title, edge = check.edge, kelly = size.kelly, reason = %reason, "no trade");
+            record.result = Err(reason);
+            return record;
+        }
+        info!(
+            market = market.id(),
+            title = %signal.title,
+            score = signal.score,
+            belief = signal.belief,
+            mid = check.quote.mid(),
+            edge = check.edge,
+            threshold = check.threshold,
+            outcome = ?trade.outcome,
+            price = trade.price,
+            kelly = size.kelly,
+            fraction = size.fraction,
+            contracts = size.contracts,
+            cap = %size.cap,
+            "trade"
+        );
+        record.result = Ok(self.order(signal, &check.quote, &trade, token_id, size.contracts));
+        record
+    }
+
+    fn order(&self, signal: &PendingSignal, quote: &Quote, trade: &Trade, token_id: &str, size: f64) -> Order {
+        let market = &signal.market;
+        let time_in_force = self.cfg.trade.time_in_force;
+        Order {
+            // one order per headline and market, whatever feed repeats it
+            client_order_id: format!("{:016x}-{}", signal.news_id, market.id()),
+            market_id: market.id().to_string(),
+            side: trade.side,
+            outcome: trade.outcome,
+            token_id: token_id.to_string(),
+            order_type: OrderType::Limit,
+            time_in_force,
+            ttl: (time_in_force == TimeInForce::Gtd).then_some(self.cfg.trade.order_ttl),
+            post_only: false,
+            neg_risk: market.market.neg_risk,
+            price: trade.price as f32,
+            size: size as f32,
+            intent: Some(self.edge.intent(signal.belief, quote, trade)),
+        }
+    }
+
+    /// Contracts of an outcome held and not already being sold.
+    fn unsold(&self, market_id: &str, outcome: Outcome) -> f64 {
+        let held = self.holdings.get(&(market_id.to_string(), outcome)).copied().unwrap_or(0.0);
+        let open = self.bus.open_orders.borrow();
+        let selling = |o: &Order| o.side == Side::Sell && o.market_id == market_id && o.outcome == outcome;
+        let resting: f64 = open.values().filter(|s| selling(&s.order)).map(|s| s.remaining()).sum();
+        let in_flight: f64 = self
+            .in_flight
+            .iter()
+            .filter(|(id, (o, _))| selling(o) && !open.contains_key(*id))
+            .map(|(_, (o, _))| o.size as f64)
+            .sum();
+        held - resting - in_flight
+    }
+
+    /// Filled notional plus the buys still resting in open orders or on their way there.
+    fn committed(&self) -> Exposure {
+        let mut exposure = self.exposure.clone();
+        let open = self.bus.open_orders.borrow();
+        for state in open.values().filter(|s| s.order.side == Side::Buy) {
+            let order = &state.order;
+            let event_id = self.index.get(&order.market_id).map(|m| m.event_id.as_str());
+            exposure.add(&order.market_id, event_id, state.remaining() * order.price as f64);
+        }
+        for (id, (order, _)) in &self.in_flight {
+            if order.side == Side::Buy && !open.contains_key(id) {
+                let event_id = self.index.get(&order.market_id).map(|m| m.event_id.as_str());
+                exposure.add(&order.market_id, event_id, order.size as f64 * order.price as f64);
+            }
+        }
+        exposure
+    }
+
+    /// Forgets in-flight orders that reached `open_orders`, and any older than the order ttl,
+    /// which were rejected before they could.
+    fn prune_in_flight(&mut self) {
+        let open = self.bus.open_orders.borrow();
+        let ttl = self.cfg.trade.order_ttl;
+        self.in_flight.retain(|id, (.., sent)| !open.contains_key(id) && sent.elapsed() < ttl);
+    }
+
+    /// Adds a market's outcome to its decision, which is ready once nothing is left waiting.
+    fn settle(&mut self, decision_id: u64, record: MarketDecision) {
+        let Some(open) = self.open_decisions.get_mut(&decision_id) else { return };
+        open.decision.markets.push(record);
+        open.waiting -= 1;
+        if open.waiting == 0
+            && let Some(open) = self.open_decisions.remove(&decision_id)
+        {
+            self.finish(open.decision);
+        }
+    }
+
+    fn finish(&mut self, mut decision: Decision) {
+        decision.decided_at = Utc::now();
+        self.decisions.push(decision);
+    }
+
+    /// Drops signals whose quote never came.
+    fn expire_pending(&mut self) {
+        let timeout = self.cfg.trade.quote_timeout;
+        let mut expired = Vec::new();
+        self.pending.retain(|_, signals| {
+            expired.extend(signals.extract_if(.., |s| s.requested_at.elapsed() >= timeout));
+            !signals.is_empty()
+        });
+        for signal in expired {
+            let reason = NoTradeReason::QuoteTimeout;
+            info!(market = signal.market.id(), title = %signal.title, reason = %reason, "no trade");
+            self.settle(signal.decision_id, MarketDecision::new(&signal, reason));
+        }
+    }
+
+    async fn publish_decisions(&mut self) -> Result<()> {
+        for decision in std::mem::take(&mut self.decisions) {
+            self.bus.decisions.publish(decision).await?;
+        }
+        Ok(())
+    }
+
+    /// Selects markets for the headline and asks for their quotes; the decision is made when
+    /// they arrive in `decide_from_tick`.
+    fn decide_from_news(&mut self, news: &EnrichedNews) -> Vec<MarketDataRequest> {
+        // same story from another feed: confirms the first arrival, carries no new information
+        if !news.story.first {
+            debug!(story = news.story.id, feeds = ?news.story.feeds, title = %news.raw.title, "story confirmation");
+            return Vec::new();
+        }
+        debug!(
+            feed = %news.raw.feed,
+            latency_ms = ?news.latency_ms,
+            tokens = ?news.tokens.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            central_banks = ?news.entities.central_banks,
+            countries = ?news.entities.countries,
+            leaders = ?news.entities.leaders,
+            terms = ?news.entities.economic_terms,
+            tickers = ?news.entities.tickers,
+            quantities = ?news.quantities.iter().map(|q| &q.kind).collect::<Vec<_>>(),
+            windows = ?news.time_windows.iter().map(|w| (w.start, w.end)).collect::<Vec<_>>(),
+            "enriched news"
+        );
+        self.next_decision_id += 1;
+        let mut decision = Decision {
+            id: self.next_decision_id,
+            news_id: news.content_hash,
+            title: news.raw.title.clone(),
+            received_at: news.received_at,
+            decided_at: news.received_at,
+            candidates: Vec::new(),
+            dropped: Vec::new(),
+            markets: Vec::new(),
+        };
+
+        let (candidates, dropped) = self.candidates(news);
+        decision.dropped = dropped
+            .iter()
+            .map(|d| DropRecord {
+                market_id: d.candidate.market.id().to_string(),
+                title: d.candidate.market.title().to_string(),
+                bm25: d.candidate.bm25,
+                filter: d.filter,
+                reason: d.reason.clone(),
+            })
+            .collect();
+        let scored = self.scorer.score(news, candidates);
+        for c in &scored {
+            let b = &c.breakdown;
+            debug!(
+                market = c.candidate.market.id(),
+                title = c.candidate.market.title(),
+                score = b.score,
+                bm25 = c.candidate.bm25,
+                bm25_norm = b.bm25_norm,
+                entity_overlap = b.entity_overlap,
+                number_overlap = b.number_overlap,
+                time_compat = b.time_compat,
+                liquidity = b.liquidity,
+                staleness = b.staleness,
+                "candidate scored"
+            );
+        }
+        decision.candidates = scored
+            .iter()
+            .map(|c| CandidateRecord {
+                market_id: c.candidate.market.id().to_string(),
+                title: c.candidate.market.title().to_string(),
+                bm25: c.candidate.bm25,
+                breakdown: c.breakdown.clone(),
+            })
+            .collect();
+        let selected = if scored.is_empty() { Vec::new() } else { self.selector.select(scored) };
+        if !selected.is_empty() {
+            info!(
+                title = %news.raw.title,
+                selected = ?selected.iter().map(|c| (c.candidate.market.title(), c.breakdown.score)).collect::<Vec<_>>(),
+                "markets selected"
+            );
+        }
+        let requested_at = Instant::now();
+        let signals: Vec<PendingSignal> = selected
+            .iter()
+            .map(|c| {
+                let belief = self.calibrator.probability(c.breakdown.score, c.candidate.market);
+                debug!(market = c.candidate.market.id(), score = c.breakdown.score, belief, "belief");
+                PendingSignal {
+                    decision_id: decision.id,
+                    news_id: news.content_hash,
+                    title: news.raw.title.clone(),
+                    market: c.candidate.market.clone(),
+                    score: c.breakdown.score,
+                    belief,
+                    requested_at,
+                }
+            })
+            .collect();
+        if signals.is_empty() {
+            self.finish(decision);
+            return Vec::new();
+        }
+
+        self.open_decisions.insert(decision.id, OpenDecision { decision, waiting: signals.len() });
+        let mut requests = Vec::with_capacity(signals.len());
+        for signal in signals {
+            let market_id = signal.market.id().to_string();
+            let waiting = self.pending.entry(market_id.clone()).or_default();
+            // one request answers every signal already waiting on this market
+            if waiting.is_empty() {
+                requests.push(MarketDataRequest { market_id });
+            }
+            waiting.push(signal);
+        }
+        requests
+    }
+
+    fn decide_from_executions(&mut self, execution: &Execution) -> Option<Order> {
+        self.in_flight.remove(&execution.client_order_id);
+        // a sale gives back the notional it fetched
+        let contracts = match execution.side {
+            Side::Buy => execution.filled as f64,
+            Side::Sell => -(execution.filled as f64),
+        };
+        *self.holdings.entry((execution.market_id.clone(), execution.outcome)).or_default() += contracts;
+        let notional = contracts * execution.avg_px as f64;
+        if notional != 0.0 {
+            let event_id = self.index.get(&execution.market_id).map(|m| m.event_id.as_str());
+            self.exposure.add(&execution.market_id, event_id, notional);
+        }
         // TODO: real logic
         None
     }
@@ -38,8 +521,13 @@ impl Actor for StrategyActor {
 
         // Subscribe to both broadcast streams
         let mut md_rx   = self.bus.market_data.subscribe(); // broadcast::Receiver<Arc<MarketDataSnap>>
-        let mut news_rx = self.bus.raw_news.subscribe();     // broadcast::Receiver<Arc<RawNews>>
+        let mut news_rx = self.bus.enriched_news.subscribe(); // broadcast::Receiver<Arc<EnrichedNews>>
         let mut executions_rx = self.bus.executions.subscribe();     // broadcast::Receiver<Arc<Executions>>
+        let mut changes_rx = self.bus.market_changes.subscribe(); // broadcast::Receiver<Arc<MarketChange>>
+        let mut expiry = tokio::time::interval(self.cfg.trade.quote_timeout / 2);
+        // same cadence as the EnrichmentActor, so news and markets are tagged alike
+        let reload = self.entities.reload_interval();
+        let mut reload_tick = tokio::time::interval_at(tokio::time::Instant::now() + reload, reload);
 
         loop {
             tokio::select! {
@@ -53,10 +541,11 @@ impl Actor for StrategyActor {
                 res = md_rx.recv() => {
                     match res {
                         Ok(snap) => {
-                            if let Some(order) = self.decide_from_tick(&snap) {
+                            for order in self.decide_from_tick(&snap) {
                                 // Publish order to orders topic
                                 self.bus.orders.publish(order).await?;
                             }
+                            self.publish_decisions().await?;
                         }
                         Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
                             warn!(lagged = n, "StrategyActor lagged on market_data");
@@ -68,19 +557,46 @@ impl Actor for StrategyActor {
                     }
                 }
 
+                // Signals whose quote never arrived
+                _ = expiry.tick() => {
+                    self.expire_pending();
+                    self.publish_decisions().await?;
+                }
+
+                _ = reload_tick.tick() => self.reload_entities(),
+
+                // Market catalogue path
+                res = changes_rx.recv() => {
+                    match res {
+                        Ok(change) => self.on_market_change(&change),
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                            warn!(lagged = n, "StrategyActor lagged on market_changes");
+                            if !self.resync_requested {
+                                self.resync_requested = true;
+                                self.bus.market_snapshot_request.publish(MarketSnapshotRequest).await?;
+                            }
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                            error!("market_changes stream closed; exiting StrategyActor");
+                            break;
+                        }
+                    }
+                }
+
                 // News path
                 res = news_rx.recv() => {
                     match res {
                         Ok(news) => {
-                            if let Some(order) = self.decide_from_news(&news) {
-                                self.bus.orders.publish(order).await?;
+                            for request in self.decide_from_news(&news) {
+                                self.bus.market_data_request.publish(request).await?;
                             }
+                            self.publish_decisions().await?;
                         }
                         Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
-                            warn!(lagged = n, "StrategyActor lagged on raw_news");
+                            warn!(lagged = n, "StrategyActor lagged on enriched_news");
                         }
                         Err(tokio::sync::broadcast::error::RecvError::Closed) => {
-                            error!("raw_news stream closed; exiting StrategyActor");
+                            error!("enriched_news stream closed; exiting StrategyActor");
                             break;
                         }
                     }
@@ -0,0 +1,126 @@
+use std::fmt;
+use serde::Serialize;
+use crate::config::config::TradeCfg;
+use crate::core::types::{MarketDataSnap, OrderIntent, Outcome, Side, Urgency};
+use crate::strategy::sizing::SizeCap;
+
+#[derive(Clone, Debug, Serialize)]
+pub enum NoTradeReason {
+    /// |edge| does not clear tau plus half the spread and the fee buffer.
+    EdgeBelowThreshold { edge: f64, threshold: f64 },
+    /// Snapshot has no usable two-sided YES book.
+    NoQuote,
+    /// No snapshot arrived within `quoteTimeout`.
+    QuoteTimeout,
+    /// Market lists no CLOB token for the outcome to buy.
+    NoToken,
+    /// Sizing came to less than one contract.
+    ZeroSize { cap: SizeCap },
+}
+
+impl fmt::Display for NoTradeReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NoTradeReason::EdgeBelowThreshold { edge, threshold } => {
+                write!(f, "|edge| {:.4} <= threshold {threshold:.4}", edge.abs())
+            }
+            NoTradeReason::NoQuote => write!(f, "no valid bid/ask"),
+            NoTradeReason::QuoteTimeout => write!(f, "no quote before timeout"),
+            NoTradeReason::NoToken => write!(f, "no token for outcome"),
+            NoTradeReason::ZeroSize { cap } => write!(f, "zero size, bound by {cap}"),
+        }
+    }
+}
+
+/// YES book as seen by the rule.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Quote {
+    pub bid: f64,
+    pub ask: f64,
+    pub bid_size: f64,
+    pub ask_size: f64,
+}
+
+impl Quote {
+    /// `None` unless 0 < bid <= ask < 1.
+    pub fn from_snap(snap: &MarketDataSnap) -> Option<Self> {
+        let (bid, ask) = (snap.best_bid as f64, snap.best_ask as f64);
+        let (bid_size, ask_size) = (snap.bid_size as f64, snap.ask_size as f64);
+        (bid > 0.0 && bid <= ask && ask < 1.0).then_some(Self { bid, ask, bid_size, ask_size })
+    }
+
+    pub fn mid(&self) -> f64 { (self.bid + self.ask) / 2.0 }
+
+    pub fn spread(&self) -> f64 { self.ask - self.bid }
+}
+
+/// Buy `outcome` at `price` (the YES ask, or one minus the YES bid for NO), against `depth`
+/// contracts resting on that side; or sell it at its bid.
+#[derive(Clone, Copy, Debug)]
+pub struct Trade {
+    pub side: Side,
+    pub outcome: Outcome,
+    pub price: f64,
+    pub depth: f64,
+}
+
+impl Trade {
+    /// The same view taken by selling the other outcome, at its bid: one minus this price,
+    /// against the same YES level.
+    pub fn sell_other(&self) -> Self {
+        Self { side: Side::Sell, outcome: self.outcome.other(), price: 1.0 - self.price, depth: self.depth }
+    }
+}
+
+/// Belief against the YES mid. The threshold widens by half the spread and the fee buffer so
+/// a trade only fires when the edge survives crossing the book.
+#[derive(Clone, Debug)]
+pub struct EdgeRule {
+    tau: f64,
+    fee_buffer: f64,
+    urgency: Urgency,
+}
+
+#[derive(Clone, Debug)]
+pub struct EdgeCheck {
+    pub quote: Quote,
+    /// `p - mid`.
+    pub edge: f64,
+    pub threshold: f64,
+    pub decision: Result<Trade, NoTradeReason>,
+}
+
+impl EdgeRule {
+    pub fn new(cfg: &TradeCfg) -> Self {
+        Self { tau: cfg.tau, fee_buffer: cfg.fee_buffer, urgency: cfg.urgency }
+    }
+
+    pub fn evaluate(&self, belief: f64, snap: &MarketDataSnap) -> Result<EdgeCheck, NoTradeReason> {
+        let quote = Quote::from_snap(snap).ok_or(NoTradeReason::NoQuote)?;
+        let edge = belief - quote.mid();
+        let threshold = self.tau + quote.spread() / 2.0 + self.fee_buffer;
+        let decision = if edge > threshold {
+            Ok(Trade { side: Side::Buy, outcome: Outcome::Yes, price: quote.ask, depth: quote.ask_size })
+        } else if edge < -threshold {
+            Ok(Trade { side: Side::Buy, outcome: Outcome::No, price: 1.0 - quote.bid, depth: quote.bid_size })
+        } else {
+            Err(NoTradeReason::EdgeBelowThreshold { edge, threshold })
+        };
+        Ok(EdgeCheck { quote, edge, threshold, decision })
+    }
+
+    /// Latitude execution gets for a trade, in the traded outcome's prices: aim at the mid,
+    /// pay at most (sell for at least) what still leaves tau plus the fee buffer of edge.
+    pub fn intent(&self, belief: f64, quote: &Quote, trade: &Trade) -> OrderIntent {
+        let (fair, mid) = match trade.outcome {
+            Outcome::Yes => (belief, quote.mid()),
+            Outcome::No => (1.0 - belief, 1.0 - quote.mid()),
+        };
+        let margin = self.tau + self.fee_buffer;
+        let (max_price, edge) = match trade.side {
+            Side::Buy => (fair - margin, fair - mid),
+            Side::Sell => (fair + margin, mid - fair),
+        };
+        OrderIntent { target_price: mid as f32, max_price: max_price as f32, urgency: self.urgency, edge }
+    }
+}
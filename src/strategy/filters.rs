@@ -0,0 +1,216 @@
+use std::fmt;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use crate::config::config::{
+    CategoryFilterCfg, CategoryRuleCfg, DateFilterCfg, EntityFilterCfg, FiltersCfg, LiquidityFilterCfg,
+};
+use crate::core::types::{EnrichedNews, IndexedMarket};
+use crate::strategy::types::Candidate;
+use crate::text::entities::EntityKind;
+
+#[derive(Clone, Debug, Serialize)]
+pub enum DropReason {
+    /// Market resolved before the headline arrived.
+    Expired { end_date: DateTime<Utc> },
+    /// Market end date is far from every date the headline mentions.
+    OutsideWindows { end_date: DateTime<Utc> },
+    /// Headline triggered these category rules and the market matches none of them.
+    Category { rules: Vec<String> },
+    NoEntityOverlap,
+    LowOpenInterest { value: Option<f64>, min: f64 },
+    LowVolume { value: Option<f64>, min: f64 },
+    WideSpread { value: f64, max: f64 },
+    NoQuote,
+}
+
+impl fmt::Display for DropReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DropReason::Expired { end_date } => write!(f, "expired at {end_date}"),
+            DropReason::OutsideWindows { end_date } => write!(f, "resolves {end_date}, outside headline windows"),
+            DropReason::Category { rules } => write!(f, "not in category {}", rules.join("/")),
+            DropReason::NoEntityOverlap => write!(f, "no shared entity"),
+            DropReason::LowOpenInterest { value, min } => write!(f, "open interest {value:?} < {min}"),
+            DropReason::LowVolume { value, min } => write!(f, "24h volume {value:?} < {min}"),
+            DropReason::WideSpread { value, max } => write!(f, "spread {value:.3} > {max:.3}"),
+            DropReason::NoQuote => write!(f, "no bid/ask"),
+        }
+    }
+}
+
+/// One hard filter. `Err` carries why the market was dropped.
+pub trait Filter: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn check(&self, news: &EnrichedNews, market: &IndexedMarket) -> Result<(), DropReason>;
+}
+
+#[derive(Clone, Debug)]
+pub struct Dropped<'a> {
+    pub candidate: Candidate<'a>,
+    pub filter: &'static str,
+    pub reason: DropReason,
+}
+
+/// Enabled filters, cheapest first. A candidate is dropped by the first filter it fails.
+pub struct FilterChain {
+    filters: Vec<Box<dyn Filter>>,
+}
+
+impl FilterChain {
+    pub fn new(cfg: &FiltersCfg) -> Self {
+        let mut filters: Vec<Box<dyn Filter>> = Vec::new();
+        if cfg.liquidity.enabled {
+            filters.push(Box::new(LiquidityFilter { cfg: cfg.liquidity.clone() }));
+        }
+        if cfg.date.enabled {
+            filters.push(Box::new(DateFilter::new(&cfg.date)));
+        }
+        if cfg.category.enabled {
+            filters.push(Box::new(CategoryFilter::new(&cfg.category)));
+        }
+        if cfg.entities.enabled {
+            filters.push(Box::new(EntityOverlapFilter { cfg: cfg.entities.clone() }));
+        }
+        Self { filters }
+    }
+
+    pub fn apply<'a>(&self, news: &EnrichedNews, candidates: Vec<Candidate<'a>>) -> (Vec<Candidate<'a>>, Vec<Dropped<'a>>) {
+        let mut kept = Vec::with_capacity(candidates.len());
+        let mut dropped = Vec::new();
+        'candidates: for candidate in candidates {
+            for filter in &self.filters {
+                if let Err(reason) = filter.check(news, candidate.market) {
+                    dropped.push(Dropped { candidate, filter: filter.name(), reason });
+                    continue 'candidates;
+                }
+            }
+            kept.push(candidate);
+        }
+        (kept, dropped)
+    }
+}
+
+/// Min open interest and 24h volume, max spread. Missing figures count as zero liquidity.
+pub struct LiquidityFilter {
+    cfg: LiquidityFilterCfg,
+}
+
+impl Filter for LiquidityFilter {
+    fn name(&self) -> &'static str { "liquidity" }
+
+    fn check(&self, _news: &EnrichedNews, market: &IndexedMarket) -> Result<(), DropReason> {
+        let m = &market.market;
+        if market.open_interest.unwrap_or(0.0) < self.cfg.min_open_interest {
+            return Err(DropReason::LowOpenInterest { value: market.open_interest, min: self.cfg.min_open_interest });
+        }
+        if m.volume_24hr.unwrap_or(0.0) < self.cfg.min_volume_24h {
+            return Err(DropReason::LowVolume { value: m.volume_24hr, min: self.cfg.min_volume_24h });
+        }
+        let spread = match (m.spread, m.best_bid, m.best_ask) {
+            (Some(s), ..) => s,
+            (None, Some(bid), Some(ask)) => ask - bid,
+            _ => return Err(DropReason::NoQuote),
+        };
+        if spread > self.cfg.max_spread {
+            return Err(DropReason::WideSpread { value: spread, max: self.cfg.max_spread });
+        }
+        Ok(())
+    }
+}
+
+/// Drops expired markets, and markets resolving far from the dates the headline mentions.
+/// Headlines without dates and markets without an end date pass.
+pub struct DateFilter {
+    early: chrono::Duration,
+    late: chrono::Duration,
+}
+
+impl DateFilter {
+    pub fn new(cfg: &DateFilterCfg) -> Self {
+        Self {
+            early: chrono::Duration::from_std(cfg.early_tolerance).unwrap_or(chrono::Duration::MAX),
+            late: chrono::Duration::from_std(cfg.late_tolerance).unwrap_or(chrono::Duration::MAX),
+        }
+    }
+}
+
+impl Filter for DateFilter {
+    fn name(&self) -> &'static str { "date" }
+
+    fn check(&self, news: &EnrichedNews, market: &IndexedMarket) -> Result<(), DropReason> {
+        let Some(end_date) = market.end_date else { return Ok(()) };
+        if end_date < news.received_at {
+            return Err(DropReason::Expired { end_date });
+        }
+        if news.time_windows.is_empty() {
+            return Ok(());
+        }
+        let near = news.time_windows.iter().any(|w| {
+            let from = w.start.checked_sub_signed(self.early).unwrap_or(DateTime::<Utc>::MIN_UTC);
+            let to = w.end.checked_add_signed(self.late).unwrap_or(DateTime::<Utc>::MAX_UTC);
+            (from..=to).contains(&end_date)
+        });
+        if near { Ok(()) } else { Err(DropReason::OutsideWindows { end_date }) }
+    }
+}
+
+/// Rules triggered by the headline's entities restrict markets to the rules' tags.
+pub struct CategoryFilter {
+    rules: Vec<CategoryRuleCfg>,
+}
+
+impl CategoryFilter {
+    pub fn new(cfg: &CategoryFilterCfg) -> Self {
+        let mut rules = cfg.rules.clone();
+        for rule in &mut rules {
+            rule.tags.iter_mut().for_each(|t| *t = t.to_lowercase());
+        }
+        Self { rules }
+    }
+}
+
+impl Filter for CategoryFilter {
+    fn name(&self) -> &'static str { "category" }
+
+    fn check(&self, news: &EnrichedNews, market: &IndexedMarket) -> Result<(), DropReason> {
+        let entities = &news.entities;
+        let triggered: Vec<&CategoryRuleCfg> = self
+            .rules
+            .iter()
+            .filter(|r| {
+                r.kinds.iter().any(|k| !entities.get(*k).is_empty())
+                    || r.entities.iter().any(|id| EntityKind::ALL.iter().any(|k| entities.get(*k).contains(id)))
+            })
+            .collect();
+        if triggered.is_empty() {
+            return Ok(());
+        }
+
+        let labels: Vec<String> = market.tags.iter().chain(&market.category).map(|t| t.to_lowercase()).collect();
+        if triggered.iter().any(|r| r.tags.iter().any(|t| labels.contains(t))) {
+            Ok(())
+        } else {
+            Err(DropReason::Category { rules: triggered.iter().map(|r| r.name.clone()).collect() })
+        }
+    }
+}
+
+/// Headline and market must share at least one entity of the configured kinds.
+pub struct EntityOverlapFilter {
+    cfg: EntityFilterCfg,
+}
+
+impl Filter for EntityOverlapFilter {
+    fn name(&self) -> &'static str { "entities" }
+
+    fn check(&self, news: &EnrichedNews, market: &IndexedMarket) -> Result<(), DropReason> {
+        let kinds: &[EntityKind] = &self.cfg.kinds;
+        if kinds.iter().all(|k| news.entities.get(*k).is_empty()) {
+            return Ok(());
+        }
+        let shared = kinds
+            .iter()
+            .any(|k| !news.entities.get(*k).is_disjoint(market.entities.get(*k)));
+        if shared { Ok(()) } else { Err(DropReason::NoEntityOverlap) }
+    }
+}
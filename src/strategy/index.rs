@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use crate::config::config::IndexCfg;
+use crate::core::types::IndexedMarket;
+use crate::text::normalizer::{Normalizer, Token};
+
+// Field order everywhere: title, description, tags, outcomes.
+const FIELDS: usize = 4;
+
+// Removed documents stay in the postings as tombstones until they make up this share of the index.
+const COMPACT_RATIO: usize = 4;
+const COMPACT_MIN: usize = 1024;
+
+struct Posting {
+    doc: u32,
+    tf: [u16; FIELDS],
+}
+
+struct Doc {
+    market: IndexedMarket,
+    lengths: [u32; FIELDS],
+    terms: Vec<u32>,
+}
+
+/// In-memory BM25F index over market title (question + event title), description, tags and
+/// outcomes. Market text goes through the same `Normalizer` as the news, so query tokens can
+/// be looked up directly. Markets are added, re-indexed and removed one at a time as catalogue
+/// changes arrive; each operation only touches that market's terms.
+pub struct MarketIndex {
+    k1: f64,
+    b: f64,
+    boosts: [f64; FIELDS],
+    normalizer: Normalizer,
+    terms: HashMap<String, u32>,
+    postings: Vec<Vec<Posting>>,
+    /// Live documents per term; postings may still hold tombstones.
+    df: Vec<u32>,
+    docs: Vec<Option<Doc>>,
+    total_len: [u64; FIELDS],
+    by_id: HashMap<String, u32>,
+}
+
+impl MarketIndex {
+    pub fn new(cfg: &IndexCfg) -> Self {
+        let boosts = &cfg.boosts;
+        Self {
+            k1: cfg.k1,
+            b: cfg.b,
+            boosts: [boosts.title, boosts.description, boosts.tags, boosts.outcomes],
+            normalizer: Normalizer::default(),
+            terms: HashMap::new(),
+            postings: Vec::new(),
+            df: Vec::new(),
+            docs: Vec::new(),
+            total_len: [0; FIELDS],
+            by_id: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_id.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_id.is_empty()
+    }
+
+    pub fn get(&self, market_id: &str) -> Option<&IndexedMarket> {
+        self.by_id.get(market_id).and_then(|&d| self.docs[d as usize].as_ref()).map(|d| &d.market)
+    }
+
+    pub fn markets(&self) -> impl Iterator<Item = &IndexedMarket> {
+        self.docs.iter().flatten().map(|d| &d.market)
+    }
+
+    /// Indexes a market, replacing any previous version of it.
+    pub fn insert(&mut self, market: IndexedMarket) {
+        self.remove(market.id());
+        let doc = self.docs.len() as u32;
+
+        let mut title = self.normalizer.tokenize(market.title());
+        if let Some(event_title) = &market.event_title
+            && market.market.question.as_deref() != Some(event_title.as_str())
+        {
+            title.extend(self.normalizer.tokenize(event_title));
+        }
+        let description = market.market.description.as_deref().unwrap_or_default();
+        let fields: [Vec<Token>; FIELDS] = [
+            title,
+            self.normalizer.tokenize(description),
+            market.tags.iter().chain(&market.category).flat_map(|t| self.normalizer.tokenize(t)).collect(),
+            market.market.outcomes.iter().flat_map(|o| self.normalizer.tokenize(o)).collect(),
+        ];
+
+        let mut tfs: HashMap<&str, [u16; FIELDS]> = HashMap::new();
+        let mut lengths = [0u32; FIELDS];
+        for (f, toks) in fields.iter().enumerate() {
+            lengths[f] = toks.len() as u32;
+            self.total_len[f] += toks.len() as u64;
+            for t in toks {
+                let tf = &mut tfs.entry(t.text.as_str()).or_default()[f];
+                *tf = tf.saturating_add(1);
+            }
+        }
+
+        let mut terms = Vec::with_capacity(tfs.len());
+        for (term, tf) in tfs {
+            let id = match self.terms.get(term) {
+                Some(&id) => id,
+                None => {
+                    let id = self.postings.len() as u32;
+                    self.terms.insert(term.to_string(), id);
+                    self.postings.push(Vec::new());
+                    self.df.push(0);
+                    id
+                }
+            };
+            self.postings[id as usize].push(Posting { doc, tf });
+            self.df[id as usize] += 1;
+            terms.push(id);
+        }
+
+        self.by_id.insert(market.id().to_string(), doc);
+        self.docs.push(Some(Doc { market, lengths, terms }));
+    }
+
+    /// Swaps the stored market without re-indexing, for changes that don't touch its text
+    /// (prices, liquidity, ...). Returns false if the market isn't indexed.
+    pub fn update(&mut self, market: IndexedMarket) -> bool {
+        let Some(&d) = self.by_id.get(market.id()) else { return false };
+        match &mut self.docs[d as usize] {
+            Some(doc) => {
+                doc.market = market;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn remove(&mut self, market_id: &str) -> bool {
+        let Some(d) = self.by_id.remove(market_id) else { return false };
+        let Some(doc) = self.docs[d as usize].take() else { return false };
+        for (total, len) in self.total_len.iter_mut().zip(doc.lengths) {
+            *total -= len as u64;
+        }
+        for t in doc.terms {
+            self.df[t as usize] -= 1;
+        }
+        if self.docs.len() - self.len() >= COMPACT_MIN.max(self.len() / COMPACT_RATIO) {
+            self.compact();
+        }
+        true
+    }
+
+    /// Drops tombstones from the postings and renumbers the live documents.
+    fn compact(&mut self) {
+        let mut remap = vec![u32::MAX; self.docs.len()];
+        let mut docs = Vec::with_capacity(self.len());
+        for (old, doc) in self.docs.drain(..).enumerate() {
+            if let Some(doc) = doc {
+                remap[old] = docs.len() as u32;
+                docs.push(Some(doc));
+            }
+        }
+        for list in &mut self.postings {
+            list.retain_mut(|p| {
+                p.doc = remap[p.doc as usize];
+                p.doc != u32::MAX
+            });
+        }
+        for d in self.by_id.values_mut() {
+            *d = remap[*d as usize];
+        }
+        self.docs = docs;
+    }
+
+    /// Top `k` markets for the query, best first.
+    pub fn search<'a>(&self, query: impl IntoIterator<Item = &'a Token>, k: usize) -> Vec<(String, f64)> {
+        let n = self.len() as f64;
+        if n == 0.0 || k == 0 {
+            return Vec::new();
+        }
+        let avg: [f64; FIELDS] = std::array::from_fn(|f| self.total_len[f] as f64 / n);
+
+        let mut terms: Vec<u32> = query.into_iter().filter_map(|t| self.terms.get(&t.text).copied()).collect();
+        terms.sort_unstable();
+        terms.dedup();
+
+        let mut scores = vec![0.0f64; self.docs.len()];
+        let mut touched = Vec::new();
+        for term in terms {
+            let df = self.df[term as usize] as f64;
+            if df == 0.0 { continue; }
+            let idf = (1.0 + (n - df + 0.5) / (df + 0.5)).ln();
+
+            for p in &self.postings[term as usize] {
+                let Some(doc) = &self.docs[p.doc as usize] else { continue };
+                // BM25F: boost and length-normalize per field, then saturate once
+                let mut tf = 0.0;
+                for (f, &field_tf) in p.tf.iter().enumerate() {
+                    if field_tf == 0 { continue; }
+                    let norm = if avg[f] > 0.0 { 1.0 - self.b + self.b * doc.lengths[f] as f64 / avg[f] } else { 1.0 };
+                    tf += self.boosts[f] * field_tf as f64 / norm;
+                }
+                if tf == 0.0 { continue; }
+
+                let score = &mut scores[p.doc as usize];
+                if *score == 0.0 {
+                    touched.push(p.doc);
+                }
+                *score += idf * tf / (self.k1 + tf);
+            }
+        }
+
+        let mut hits: Vec<(u32, f64)> = touched.into_iter().map(|d| (d, scores[d as usize])).collect();
+        if hits.len() > k {
+            hits.select_nth_unstable_by(k - 1, |a, b| b.1.total_cmp(&a.1));
+            hits.truncate(k);
+        }
+        hits.sort_unstable_by(|a, b| b.1.total_cmp(&a.1));
+        hits.into_iter()
+            .filter_map(|(d, s)| self.docs[d as usize].as_ref().map(|doc| (doc.market.id().to_string(), s)))
+            .collect()
+    }
+}
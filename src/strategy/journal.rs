@@ -0,0 +1,64 @@
+use anyhow::{Context, Result};
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+use crate::bus::types::Bus;
+use crate::core::types::Actor;
+
+/// Appends every decision on `decisions` to a JSON lines file, one decision per line, for
+/// auditing and for joining with resolutions into `calibrate` records.
+pub struct DecisionLog {
+    pub bus: Bus,
+    pub path: String,
+    pub shutdown: CancellationToken,
+}
+
+impl DecisionLog {
+    pub fn new(bus: Bus, path: String, shutdown: CancellationToken) -> Self {
+        Self { bus, path, shutdown }
+    }
+}
+
+#[async_trait::async_trait]
+impl Actor for DecisionLog {
+    async fn run(self) -> Result<()> {
+        info!(path = %self.path, "DecisionLog started");
+        let mut rx = self.bus.decisions.subscribe();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .with_context(|| format!("opening decision log {}", self.path))?;
+
+        loop {
+            tokio::select! {
+                _ = self.shutdown.cancelled() => {
+                    info!("DecisionLog: shutdown requested");
+                    break;
+                }
+
+                res = rx.recv() => {
+                    match res {
+                        Ok(decision) => {
+                            let mut line = serde_json::to_string(&*decision)?;
+                            line.push('\n');
+                            file.write_all(line.as_bytes()).await.context("writing decision log")?;
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                            warn!(lagged = n, "DecisionLog lagged on decisions; entries lost");
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                            error!("decisions stream closed; exiting DecisionLog");
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        file.flush().await?;
+        info!("DecisionLog stopped cleanly");
+        Ok(())
+    }
+}
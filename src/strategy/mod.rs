@@ -1 +1,10 @@
 pub mod actor;
+pub mod edge;
+pub mod filters;
+pub mod index;
+pub mod journal;
+pub mod probability;
+pub mod scorer;
+pub mod select;
+pub mod sizing;
+pub mod types;
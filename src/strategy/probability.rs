@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+use crate::config::config::{CalibrationCfg, ProbabilityCfg};
+use crate::core::types::IndexedMarket;
+
+/// Maps a candidate's final score to the belief that its market resolves YES.
+pub trait Calibrator: Send + Sync {
+    fn probability(&self, score: f64, market: &IndexedMarket) -> f64;
+}
+
+/// `sigmoid(a + b * s)` shrunk toward 0.5 by λ, with (a, b, λ) chosen by the market's category,
+/// then its tags, then the default.
+pub struct LogisticCalibrator {
+    default: CalibrationCfg,
+    /// Keys lowercased.
+    categories: HashMap<String, CalibrationCfg>,
+}
+
+impl LogisticCalibrator {
+    pub fn new(cfg: &ProbabilityCfg) -> Self {
+        let categories = cfg.categories.iter().map(|(k, v)| (k.to_lowercase(), *v)).collect();
+        Self { default: cfg.default, categories }
+    }
+
+    fn params(&self, market: &IndexedMarket) -> &CalibrationCfg {
+        market
+            .category
+            .iter()
+            .chain(&market.tags)
+            .find_map(|label| self.categories.get(&label.to_lowercase()))
+            .unwrap_or(&self.default)
+    }
+}
+
+impl Calibrator for LogisticCalibrator {
+    fn probability(&self, score: f64, market: &IndexedMarket) -> f64 {
+        shrunk_logistic(self.params(market), score)
+    }
+}
+
+pub fn shrunk_logistic(c: &CalibrationCfg, score: f64) -> f64 {
+    let raw = sigmoid(c.a + c.b * score);
+    (0.5 + c.lambda * (raw - 0.5)).clamp(0.0, 1.0)
+}
+
+pub fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
@@ -0,0 +1,163 @@
+use chrono::{DateTime, Utc};
+use crate::config::config::{ScoreNormsCfg, ScoreWeightsCfg, ScorerCfg};
+use crate::core::types::{EnrichedNews, IndexedMarket};
+use crate::strategy::types::{Candidate, ScoreBreakdown, ScoredCandidate};
+use crate::text::entities::EntityKind;
+use crate::text::normalizer::TextField;
+use crate::text::numbers::{self, Quantity, QuantityKind};
+
+/// The README's linear heuristic. Every term is normalized onto [0, 1] before weighting so the
+/// weights read as shares of the score.
+pub struct Scorer {
+    weights: ScoreWeightsCfg,
+    norms: ScoreNormsCfg,
+}
+
+impl Scorer {
+    pub fn new(cfg: &ScorerCfg) -> Self {
+        Self { weights: cfg.weights.clone(), norms: cfg.norms.clone() }
+    }
+
+    /// Scores and sorts candidates, best first.
+    pub fn score<'a>(&self, news: &EnrichedNews, candidates: Vec<Candidate<'a>>) -> Vec<ScoredCandidate<'a>> {
+        let mut scored: Vec<ScoredCandidate> = candidates
+            .into_iter()
+            .map(|candidate| {
+                let breakdown = self.breakdown(news, &candidate);
+                ScoredCandidate { candidate, breakdown }
+            })
+            .collect();
+        scored.sort_by(|a, b| b.breakdown.score.total_cmp(&a.breakdown.score));
+        scored
+    }
+
+    pub fn breakdown(&self, news: &EnrichedNews, candidate: &Candidate) -> ScoreBreakdown {
+        let market = candidate.market;
+        let w = &self.weights;
+        let mut b = ScoreBreakdown {
+            bm25_norm: saturate(candidate.bm25, self.norms.bm25_scale),
+            entity_overlap: entity_overlap(news, market),
+            number_overlap: self.number_overlap(news, market),
+            time_compat: self.time_compat(news, market),
+            liquidity: self.liquidity(market),
+            staleness: self.staleness(news.received_at, market),
+            score: 0.0,
+        };
+        b.score = w.bm25 * b.bm25_norm
+            + w.entity_overlap * b.entity_overlap
+            + w.number_overlap * b.number_overlap
+            + w.time_compat * b.time_compat
+            + w.liquidity * b.liquidity
+            - w.staleness * b.staleness;
+        b
+    }
+
+    /// Share of the headline's quantities that also appear in the market title.
+    fn number_overlap(&self, news: &EnrichedNews, market: &IndexedMarket) -> f64 {
+        if news.quantities.is_empty() {
+            return 0.0;
+        }
+        let mut title = market.title().to_string();
+        if let Some(item) = &market.market.group_item_title {
+            title.push(' ');
+            title.push_str(item);
+        }
+        let in_market = numbers::extract(&title, TextField::Title);
+        let matched = news
+            .quantities
+            .iter()
+            .filter(|q| in_market.iter().any(|m| self.same_number(q, m)))
+            .count();
+        matched as f64 / news.quantities.len() as f64
+    }
+
+    /// "50bps" matches "0.5%" on the percent scale; other quantities match on their raw value
+    /// only against the same kind, so "25bps" never matches a plain 25 or 25%.
+    fn same_number(&self, a: &Quantity, b: &Quantity) -> bool {
+        if let (Some(x), Some(y)) = (a.as_percent(), b.as_percent()) {
+            return self.close(x, y);
+        }
+        same_kind(&a.kind, &b.kind) && self.close(raw_value(&a.kind), raw_value(&b.kind))
+    }
+
+    fn close(&self, x: f64, y: f64) -> bool {
+        (x - y).abs() <= self.norms.number_tolerance * x.abs().max(y.abs())
+    }
+
+    /// 1 inside a headline window, decaying exponentially with distance from the nearest one.
+    fn time_compat(&self, news: &EnrichedNews, market: &IndexedMarket) -> f64 {
+        let Some(end_date) = market.end_date else { return self.norms.time_neutral };
+        let nearest = news
+            .time_windows
+            .iter()
+            .map(|w| {
+                if end_date < w.start {
+                    w.start - end_date
+                } else if end_date > w.end {
+                    end_date - w.end
+                } else {
+                    chrono::Duration::zero()
+                }
+            })
+            .min();
+        match nearest {
+            Some(d) => (-(d.num_seconds() as f64) / self.norms.time_decay.as_secs_f64()).exp(),
+            None => self.norms.time_neutral,
+        }
+    }
+
+    /// Mean of saturated 24h volume, saturated open interest and spread tightness.
+    fn liquidity(&self, market: &IndexedMarket) -> f64 {
+        let m = &market.market;
+        let volume = saturate(m.volume_24hr.unwrap_or(0.0), self.norms.volume_scale);
+        let open_interest = saturate(market.open_interest.unwrap_or(0.0), self.norms.open_interest_scale);
+        let spread = match (m.spread, m.best_bid, m.best_ask) {
+            (Some(s), ..) => Some(s),
+            (None, Some(bid), Some(ask)) => Some(ask - bid),
+            _ => None,
+        };
+        let tightness = spread.map_or(0.0, |s| (1.0 - s / self.norms.spread_scale).clamp(0.0, 1.0));
+        (volume + open_interest + tightness) / 3.0
+    }
+
+    /// Market age relative to `staleAfter`, capped at 1.
+    fn staleness(&self, now: DateTime<Utc>, market: &IndexedMarket) -> f64 {
+        let Some(start) = market.market.start_date else { return 0.0 };
+        let age = (now - start).num_seconds().max(0) as f64;
+        (age / self.norms.stale_after.as_secs_f64()).min(1.0)
+    }
+}
+
+/// Share of the headline's entities the market also mentions.
+fn entity_overlap(news: &EnrichedNews, market: &IndexedMarket) -> f64 {
+    let (mut total, mut shared) = (0, 0);
+    for kind in EntityKind::ALL {
+        let headline = news.entities.get(kind);
+        total += headline.len();
+        shared += headline.intersection(market.entities.get(kind)).count();
+    }
+    if total == 0 { 0.0 } else { shared as f64 / total as f64 }
+}
+
+fn saturate(x: f64, half: f64) -> f64 {
+    let x = x.max(0.0);
+    x / (x + half)
+}
+
+/// Integers and decimals are both plain numbers.
+fn same_kind(a: &QuantityKind, b: &QuantityKind) -> bool {
+    use QuantityKind::*;
+    matches!(
+        (a, b),
+        (Currency { .. }, Currency { .. }) | (Year(_), Year(_)) | (Integer(_) | Decimal(_), Integer(_) | Decimal(_))
+    )
+}
+
+fn raw_value(kind: &QuantityKind) -> f64 {
+    match *kind {
+        QuantityKind::Percent(v) | QuantityKind::BasisPoints(v) | QuantityKind::Decimal(v) => v,
+        QuantityKind::Currency { amount, .. } => amount,
+        QuantityKind::Year(y) => y as f64,
+        QuantityKind::Integer(i) => i as f64,
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::{HashMap, HashSet};
+use crate::config::config::SelectCfg;
+use crate::core::types::IndexedMarket;
+use crate::strategy::types::ScoredCandidate;
+use crate::text::normalizer::Normalizer;
+
+/// Picks up to K markets per headline: candidates below the bm25 / entity thresholds are
+/// dropped, the rest are greedily re-ranked by Maximal Marginal Relevance so near-identical
+/// markets (typically siblings in one event) don't all get selected. At most `maxPerEvent`
+/// markets are taken from any one event.
+pub struct Selector {
+    cfg: SelectCfg,
+    normalizer: Normalizer,
+}
+
+impl Selector {
+    pub fn new(cfg: &SelectCfg) -> Self {
+        Self { cfg: cfg.clone(), normalizer: Normalizer::default() }
+    }
+
+    /// `scored` in any order; the result is in selection order.
+    pub fn select<'a>(&self, scored: Vec<ScoredCandidate<'a>>) -> Vec<ScoredCandidate<'a>> {
+        let mut pool: Vec<(ScoredCandidate<'a>, HashSet<String>)> = scored
+            .into_iter()
+            .filter(|c| {
+                c.breakdown.bm25_norm >= self.cfg.min_bm25 && c.breakdown.entity_overlap >= self.cfg.min_entity_overlap
+            })
+            .map(|c| {
+                let terms = self.terms(c.candidate.market);
+                (c, terms)
+            })
+            .collect();
+
+        let lambda = self.cfg.lambda;
+        let mut selected: Vec<(ScoredCandidate<'a>, HashSet<String>)> = Vec::new();
+        let mut per_event: HashMap<&str, usize> = HashMap::new();
+        while selected.len() < self.cfg.k && !pool.is_empty() {
+            let mmr = |(c, terms): &(ScoredCandidate, HashSet<String>)| {
+                let max_sim = selected
+                    .iter()
+                    .map(|(s, s_terms)| self.similarity(c.candidate.market, terms, s.candidate.market, s_terms))
+                    .fold(0.0, f64::max);
+                lambda * c.breakdown.score - (1.0 - lambda) * max_sim
+            };
+            let best = pool
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| (i, mmr(entry)))
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(i, _)| i)
+                .expect("pool is not empty");
+            let (c, terms) = pool.swap_remove(best);
+            let event = c.candidate.market.event_id.as_str();
+            let taken = per_event.entry(event).or_insert(0);
+            *taken += 1;
+            if *taken >= self.cfg.max_per_event {
+                pool.retain(|(p, _)| p.candidate.market.event_id != event);
+            }
+            selected.push((c, terms));
+        }
+        selected.into_iter().map(|(c, _)| c).collect()
+    }
+
+    /// Token-set Jaccard of the market titles, floored for markets of the same event.
+    fn similarity(&self, a: &IndexedMarket, a_terms: &HashSet<String>, b: &IndexedMarket, b_terms: &HashSet<String>) -> f64 {
+        let union = a_terms.union(b_terms).count();
+        let jaccard = if union == 0 { 0.0 } else { a_terms.intersection(b_terms).count() as f64 / union as f64 };
+        if a.event_id == b.event_id {
+            jaccard.max(self.cfg.same_event_similarity)
+        } else {
+            jaccard
+        }
+    }
+
+    fn terms(&self, market: &IndexedMarket) -> HashSet<String> {
+        self.normalizer.tokenize(market.title()).into_iter().map(|t| t.text).collect()
+    }
+}
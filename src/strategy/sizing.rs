@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+use std::fmt;
+use serde::Serialize;
+use crate::config::config::SizingCfg;
+use crate::core::types::Outcome;
+
+/// The limit that set the final fraction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum SizeCap {
+    /// κ · f* was below every cap.
+    Kelly,
+    Market,
+    Event,
+    Gross,
+    Depth,
+}
+
+impl fmt::Display for SizeCap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            SizeCap::Kelly => "kelly",
+            SizeCap::Market => "market cap",
+            SizeCap::Event => "event cap",
+            SizeCap::Gross => "gross cap",
+            SizeCap::Depth => "book depth",
+        };
+        f.write_str(s)
+    }
+}
+
+/// What one trade is sized against. Exposures are USD notional already held.
+#[derive(Clone, Copy, Debug)]
+pub struct SizingInput {
+    pub belief: f64,
+    pub outcome: Outcome,
+    /// Price paid per contract of `outcome`.
+    pub price: f64,
+    /// Contracts available at `price`.
+    pub depth: f64,
+    pub market_exposure: f64,
+    pub event_exposure: f64,
+    pub gross_exposure: f64,
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Size {
+    /// Full Kelly fraction f*, before κ.
+    pub kelly: f64,
+    /// Fraction of bankroll after κ and caps.
+    pub fraction: f64,
+    pub contracts: f64,
+    pub cap: SizeCap,
+}
+
+/// Fractional Kelly clipped by the remaining market / event / gross room and by the visible
+/// depth, as `floor(bankroll * f / price)` contracts.
+pub fn size(cfg: &SizingCfg, input: &SizingInput) -> Size {
+    let kelly = kelly_fraction(input.belief, input.outcome, input.price);
+    let room = |max_fraction: f64, exposure: f64| (max_fraction - exposure / cfg.bankroll).max(0.0);
+    let limits = [
+        (SizeCap::Kelly, cfg.kelly * kelly),
+        (SizeCap::Market, room(cfg.max_market_fraction, input.market_exposure)),
+        (SizeCap::Event, room(cfg.max_event_fraction, input.event_exposure)),
+        (SizeCap::Gross, room(cfg.max_gross_fraction, input.gross_exposure)),
+        (SizeCap::Depth, cfg.max_depth_fraction * input.depth.max(0.0) * input.price / cfg.bankroll),
+    ];
+    // first minimum wins, so Kelly is reported when a cap merely ties it
+    let (cap, fraction) = limits
+        .into_iter()
+        .fold((SizeCap::Kelly, f64::INFINITY), |best, l| if l.1 < best.1 { l } else { best });
+    let fraction = fraction.max(0.0);
+    let contracts = if input.price > 0.0 { (cfg.bankroll * fraction / input.price).floor() } else { 0.0 };
+    Size { kelly, fraction, contracts, cap }
+}
+
+/// f* = (p - y) / (1 - y) buying YES at y, (y - p) / y buying NO at 1 - y. Zero without edge.
+pub fn kelly_fraction(belief: f64, outcome: Outcome, price: f64) -> f64 {
+    if !(price > 0.0 && price < 1.0) {
+        return 0.0;
+    }
+    let f = match outcome {
+        Outcome::Yes => (belief - price) / (1.0 - price),
+        Outcome::No => {
+            let y = 1.0 - price;
+            (y - belief) / y
+        }
+    };
+    f.max(0.0)
+}
+
+/// USD notional held, by market, by event and in total.
+#[derive(Clone, Debug, Default)]
+pub struct Exposure {
+    markets: HashMap<String, f64>,
+    events: HashMap<String, f64>,
+    gross: f64,
+}
+
+impl Exposure {
+    pub fn market(&self, market_id: &str) -> f64 {
+        self.markets.get(market_id).copied().unwrap_or(0.0)
+    }
+
+    pub fn event(&self, event_id: &str) -> f64 {
+        self.events.get(event_id).copied().unwrap_or(0.0)
+    }
+
+    pub fn gross(&self) -> f64 { self.gross }
+
+    pub fn add(&mut self, market_id: &str, event_id: Option<&str>, notional: f64) {
+        *self.markets.entry(market_id.to_string()).or_default() += notional;
+        if let Some(event_id) = event_id {
+            *self.events.entry(event_id.to_string()).or_default() += notional;
+        }
+        self.gross += notional;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cfg() -> SizingCfg {
+        SizingCfg {
+            kelly: 0.25,
+            bankroll: 1000.0,
+            max_market_fraction: 0.05,
+            max_event_fraction: 0.10,
+            max_gross_fraction: 0.50,
+            max_depth_fraction: 0.25,
+        }
+    }
+
+    /// Buying YES at 0.5 with deep book and no exposure.
+    fn input(belief: f64) -> SizingInput {
+        SizingInput {
+            belief,
+            outcome: Outcome::Yes,
+            price: 0.5,
+            depth: 1000.0,
+            market_exposure: 0.0,
+            event_exposure: 0.0,
+            gross_exposure: 0.0,
+        }
+    }
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn kelly_yes() {
+        // (0.6 - 0.5) / (1 - 0.5)
+        assert!(approx(kelly_fraction(0.6, Outcome::Yes, 0.5), 0.2));
+        assert_eq!(kelly_fraction(0.4, Outcome::Yes, 0.5), 0.0);
+    }
+
+    #[test]
+    fn kelly_no() {
+        // NO at 0.6 means YES at 0.4: (0.4 - 0.3) / 0.4
+        assert!(approx(kelly_fraction(0.3, Outcome::No, 0.6), 0.25));
+        assert_eq!(kelly_fraction(0.5, Outcome::No, 0.6), 0.0);
+    }
+
+    #[test]
+    fn kelly_outside_price_range() {
+        assert_eq!(kelly_fraction(0.9, Outcome::Yes, 0.0), 0.0);
+        assert_eq!(kelly_fraction(0.9, Outcome::Yes, 1.0), 0.0);
+    }
+
+    #[test]
+    fn capped_by_kelly() {
+        // f* = 0.1, κ f* = 0.025 under every cap
+        let s = size(&cfg(), &input(0.55));
+        assert_eq!(s.cap, SizeCap::Kelly);
+        assert!(approx(s.fraction, 0.025));
+        assert_eq!(s.contracts, 50.0);
+    }
+
+    #[test]
+    fn capped_by_market() {
+        // κ f* = 0.1 against 0.05 market room
+        let s = size(&cfg(), &input(0.7));
+        assert_eq!(s.cap, SizeCap::Market);
+        assert!(approx(s.fraction, 0.05));
+        assert_eq!(s.contracts, 100.0);
+    }
+
+    #[test]
+    fn capped_by_event() {
+        let s = size(&cfg(), &SizingInput { event_exposure: 80.0, ..input(0.7) });
+        assert_eq!(s.cap, SizeCap::Event);
+        assert!(approx(s.fraction, 0.02));
+        assert_eq!(s.contracts, 40.0);
+    }
+
+    #[test]
+    fn capped_by_gross() {
+        let s = size(&cfg(), &SizingInput { gross_exposure: 490.0, ..input(0.7) });
+        assert_eq!(s.cap, SizeCap::Gross);
+        assert!(approx(s.fraction, 0.01));
+        assert_eq!(s.contracts, 20.0);
+    }
+
+    #[test]
+    fn capped_by_depth() {
+        // 0.25 * 40 contracts * 0.5 / 1000
+        let s = size(&cfg(), &SizingInput { depth: 40.0, ..input(0.7) });
+        assert_eq!(s.cap, SizeCap::Depth);
+        assert!(approx(s.fraction, 0.005));
+        assert_eq!(s.contracts, 10.0);
+    }
+
+    #[test]
+    fn no_room_left() {
+        let s = size(&cfg(), &SizingInput { market_exposure: 60.0, ..input(0.7) });
+        assert_eq!(s.cap, SizeCap::Market);
+        assert_eq!(s.fraction, 0.0);
+        assert_eq!(s.contracts, 0.0);
+    }
+}
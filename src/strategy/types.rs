@@ -0,0 +1,123 @@
+use std::time::Instant;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use crate::core::types::{IndexedMarket, Order};
+use crate::strategy::edge::{NoTradeReason, Quote};
+use crate::strategy::filters::DropReason;
+use crate::strategy::sizing::Size;
+
+/// A market retrieved for a headline, carried through filtering, scoring and selection.
+#[derive(Clone, Debug)]
+pub struct Candidate<'a> {
+    pub market: &'a IndexedMarket,
+    /// Raw BM25 score from retrieval.
+    pub bm25: f64,
+}
+
+/// Per-term values behind a candidate's score, each on [0, 1] before weighting.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ScoreBreakdown {
+    pub bm25_norm: f64,
+    pub entity_overlap: f64,
+    pub number_overlap: f64,
+    pub time_compat: f64,
+    pub liquidity: f64,
+    pub staleness: f64,
+    /// Weighted sum of the terms above.
+    pub score: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct ScoredCandidate<'a> {
+    pub candidate: Candidate<'a>,
+    pub breakdown: ScoreBreakdown,
+}
+
+/// A selected market waiting for its quote on `market_data`.
+#[derive(Clone, Debug)]
+pub struct PendingSignal {
+    pub decision_id: u64,
+    /// `content_hash` of the headline.
+    pub news_id: u64,
+    pub title: String,
+    pub market: IndexedMarket,
+    pub score: f64,
+    pub belief: f64,
+    pub requested_at: Instant,
+}
+
+/// Audit trail of one headline's evaluation, published on `decisions` once every selected
+/// market has been settled.
+#[derive(Clone, Debug, Serialize)]
+pub struct Decision {
+    pub id: u64,
+    /// `content_hash` of the headline.
+    pub news_id: u64,
+    pub title: String,
+    pub received_at: DateTime<Utc>,
+    pub decided_at: DateTime<Utc>,
+    /// Candidates that passed the filters, best first.
+    pub candidates: Vec<CandidateRecord>,
+    /// Candidates removed by a filter.
+    pub dropped: Vec<DropRecord>,
+    /// One per selected market, in settlement order.
+    pub markets: Vec<MarketDecision>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CandidateRecord {
+    pub market_id: String,
+    pub title: String,
+    pub bm25: f64,
+    pub breakdown: ScoreBreakdown,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DropRecord {
+    pub market_id: String,
+    pub title: String,
+    pub bm25: f64,
+    pub filter: &'static str,
+    pub reason: DropReason,
+}
+
+/// What happened to one selected market. Fields past `belief` are filled as far as the
+/// evaluation got.
+#[derive(Clone, Debug, Serialize)]
+pub struct MarketDecision {
+    pub market_id: String,
+    pub event_id: String,
+    pub title: String,
+    pub score: f64,
+    pub belief: f64,
+    pub quote: Option<Quote>,
+    pub edge: Option<f64>,
+    pub threshold: Option<f64>,
+    /// Kelly fraction, capped fraction and the cap that bound it.
+    pub size: Option<Size>,
+    pub result: Result<Order, NoTradeReason>,
+}
+
+impl MarketDecision {
+    pub fn new(signal: &PendingSignal, reason: NoTradeReason) -> Self {
+        Self {
+            market_id: signal.market.id().to_string(),
+            event_id: signal.market.event_id.clone(),
+            title: signal.market.title().to_string(),
+            score: signal.score,
+            belief: signal.belief,
+            quote: None,
+            edge: None,
+            threshold: None,
+            size: None,
+            result: Err(reason),
+        }
+    }
+}
+
+/// A decision still waiting on `waiting` quotes.
+#[derive(Debug)]
+pub struct OpenDecision {
+    pub decision: Decision,
+    pub waiting: usize,
+}
@@ -0,0 +1,424 @@
+use std::ops::Range;
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use crate::core::types::RawNews;
+use crate::text::normalizer::TextField;
+
+/// A concrete UTC window a headline refers to. Both ends are inclusive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub field: TextField,
+    /// Byte range of the expression in the source field.
+    pub span: Range<usize>,
+}
+
+/// Resolves "Dec 15", "next week", "by year-end", "Q4", "this Friday", "H1 2026" against the
+/// news timestamp. Calendar days are cut in `tz` (markets resolve on US/Eastern days).
+pub struct DateExtractor {
+    tz: Tz,
+}
+
+struct Word<'a> {
+    raw: &'a str,
+    lower: String,
+    start: usize,
+    end: usize,
+}
+
+// Inclusive calendar-day range plus the number of words it consumed.
+type DayRange = (NaiveDate, NaiveDate, usize);
+
+impl DateExtractor {
+    pub fn new(timezone: &str) -> Result<Self> {
+        let tz = timezone
+            .parse::<Tz>()
+            .map_err(|e| anyhow::anyhow!("invalid timezone {timezone}: {e}"))?;
+        Ok(Self { tz })
+    }
+
+    /// Windows relative to `published`, or to `received` when the feed gave no timestamp.
+    pub fn extract(&self, news: &RawNews, received: DateTime<Utc>) -> Vec<TimeWindow> {
+        let reference = news.published.unwrap_or(received);
+        let mut out = self.extract_text(&news.title, TextField::Title, reference);
+        out.extend(self.extract_text(&news.description, TextField::Description, reference));
+        out
+    }
+
+    pub fn extract_text(&self, text: &str, field: TextField, reference: DateTime<Utc>) -> Vec<TimeWindow> {
+        let today = reference.with_timezone(&self.tz).date_naive();
+        let words = split_words(text);
+        let mut out = Vec::new();
+        let mut k = 0;
+
+        while k < words.len() {
+            // "by X" / "before X" means anywhere from now until the end of X
+            let by = matches!(words[k].lower.as_str(), "by" | "before" | "until");
+            let at = if by { k + 1 } else { k };
+            let prev = at.checked_sub(1).map(|j| words[j].lower.as_str());
+
+            let Some((first, last, n)) = match_expr(&words, at, prev, today) else {
+                k += 1;
+                continue;
+            };
+
+            let end = self.day_start(last + Days::new(1)) - chrono::Duration::seconds(1);
+            let mut start = self.day_start(first);
+            if by {
+                start = start.min(reference);
+            }
+            out.push(TimeWindow { start, end, field, span: words[k].start..words[at + n - 1].end });
+            k = at + n;
+        }
+        out
+    }
+
+    fn day_start(&self, d: NaiveDate) -> DateTime<Utc> {
+        let midnight = d.and_hms_opt(0, 0, 0).unwrap();
+        match self.tz.from_local_datetime(&midnight).earliest() {
+            Some(dt) => dt.with_timezone(&Utc),
+            // midnight skipped by a DST jump; fall back to the UTC calendar day
+            None => Utc.from_utc_datetime(&midnight),
+        }
+    }
+}
+
+fn split_words(text: &str) -> Vec<Word<'_>> {
+    let mut out = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                let raw = &text[s..i];
+                out.push(Word { raw, lower: raw.to_lowercase(), start: s, end: i });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+fn match_expr(words: &[Word], i: usize, prev: Option<&str>, today: NaiveDate) -> Option<DayRange> {
+    let w = |j: usize| words.get(i + j).map(|w| w.lower.as_str());
+    let w0 = w(0)?;
+
+    // year-end, end of (the) month, quarter end, eoy, end of December
+    match (w0, w(1), w(2), w(3)) {
+        ("eoy", ..) => return Some(period_end(Period::Year, today, 1)),
+        ("week" | "month" | "quarter" | "year", Some("end"), ..) => {
+            return Some(period_end(Period::parse(w0)?, today, 2));
+        }
+        ("end", Some("of"), Some("the"), Some(p)) if Period::parse(p).is_some() => {
+            return Some(period_end(Period::parse(p)?, today, 4));
+        }
+        ("end", Some("of"), Some(p), _) if Period::parse(p).is_some() => {
+            return Some(period_end(Period::parse(p)?, today, 3));
+        }
+        // after "end of" a month name can't be a verb, so no capitalisation rules here
+        ("end", Some("of"), Some(p), next) if month(p).is_some() => {
+            let m = month(p)?;
+            let ((_, last), used) = match next.and_then(year) {
+                Some(y) => (month_range(y, m)?, 4),
+                None => (coming_month(m, today)?, 3),
+            };
+            return Some((last, last, used));
+        }
+        _ => {}
+    }
+
+    match w0 {
+        "today" | "tonight" => return Some((today, today, 1)),
+        "tomorrow" => {
+            let d = today + Days::new(1);
+            return Some((d, d, 1));
+        }
+        "this" | "next" => {
+            let next = w0 == "next";
+            let unit = w(1)?;
+            if let Some(day) = weekday(unit, true) {
+                // "this Friday" is the coming one, "next Friday" the one in next week
+                let d = if next {
+                    monday(today) + Days::new(7 + day.num_days_from_monday() as u64)
+                } else {
+                    upcoming(today, day)
+                };
+                return Some((d, d, 2));
+            }
+            let (first, last) = this_or_next(Period::parse(unit)?, today, next);
+            return Some((first, last, 2));
+        }
+        _ => {}
+    }
+
+    if let Some(day) = weekday(w0, false) {
+        let d = upcoming(today, day);
+        return Some((d, d, 1));
+    }
+
+    // Q4 [2025], H1 [2026]
+    if let Some((kind @ ("q" | "h"), n)) = w0.split_at_checked(1)
+        && let Ok(n) = n.parse::<u32>()
+    {
+        let (explicit, used) = match w(1).and_then(year) {
+            Some(y) => (Some(y), 2),
+            None => (None, 1),
+        };
+        let (first_month, months) = match (kind, n) {
+            ("q", 1..=4) => (3 * (n - 1) + 1, 3),
+            ("h", 1..=2) => (6 * (n - 1) + 1, 6),
+            _ => return None,
+        };
+        let range = |y: i32| {
+            let first = NaiveDate::from_ymd_opt(y, first_month, 1)?;
+            Some((first, first + Months::new(months) - Days::new(1)))
+        };
+        // like months, a period more than a month past means next year's
+        let (first, last) = match explicit {
+            Some(y) => range(y)?,
+            None => match range(today.year())? {
+                (_, last) if last + Days::new(31) < today => range(today.year() + 1)?,
+                this_year => this_year,
+            },
+        };
+        return Some((first, last, used));
+    }
+
+    // Dec 15 [2025] | Dec 2025 | December | 15 Dec [2025]
+    if let Some(m) = month(w0) {
+        if let Some(d) = w(1).and_then(day_of_month) {
+            let (y, used) = match w(2).and_then(year) {
+                Some(y) => (Some(y), 3),
+                None => (None, 2),
+            };
+            let date = resolve_day(y, m, d, today)?;
+            return Some((date, date, used));
+        }
+        if let Some(y) = w(1).and_then(year) {
+            let (first, last) = month_range(y, m)?;
+            return Some((first, last, 2));
+        }
+        // "may" / "march" alone are usually verbs
+        if matches!(w0, "may" | "march") || !words[i].raw.starts_with(char::is_uppercase) {
+            return None;
+        }
+        let (first, last) = coming_month(m, today)?;
+        return Some((first, last, 1));
+    }
+    if let (Some(d), Some(m)) = (day_of_month(w0), w(1).and_then(month)) {
+        let (y, used) = match w(2).and_then(year) {
+            Some(y) => (Some(y), 3),
+            None => (None, 2),
+        };
+        let date = resolve_day(y, m, d, today)?;
+        return Some((date, date, used));
+    }
+
+    // bare years only after a preposition: "in 2026", "by 2027"
+    if let (Some(y), Some("in" | "during" | "by" | "before" | "until" | "through")) = (year(w0), prev) {
+        return Some((NaiveDate::from_ymd_opt(y, 1, 1)?, NaiveDate::from_ymd_opt(y, 12, 31)?, 1));
+    }
+    None
+}
+
+#[derive(Clone, Copy)]
+enum Period {
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl Period {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "week" => Some(Period::Week),
+            "month" => Some(Period::Month),
+            "quarter" => Some(Period::Quarter),
+            "year" => Some(Period::Year),
+            _ => None,
+        }
+    }
+}
+
+/// Calendar week (Mon-Sun), month, quarter or year containing `today`, or the one after it.
+fn this_or_next(period: Period, today: NaiveDate, next: bool) -> (NaiveDate, NaiveDate) {
+    let (first, months) = match period {
+        Period::Week => {
+            let first = monday(today) + Days::new(if next { 7 } else { 0 });
+            return (first, first + Days::new(6));
+        }
+        Period::Month => (today.with_day(1).unwrap(), 1),
+        Period::Quarter => (NaiveDate::from_ymd_opt(today.year(), (today.month0() / 3) * 3 + 1, 1).unwrap(), 3),
+        Period::Year => (NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap(), 12),
+    };
+    let first = if next { first + Months::new(months) } else { first };
+    (first, first + Months::new(months) - Days::new(1))
+}
+
+fn period_end(period: Period, today: NaiveDate, used: usize) -> DayRange {
+    let last = this_or_next(period, today, false).1;
+    (last, last, used)
+}
+
+fn monday(d: NaiveDate) -> NaiveDate {
+    d - Days::new(d.weekday().num_days_from_monday() as u64)
+}
+
+/// Next `day` on or after `today`.
+fn upcoming(today: NaiveDate, day: Weekday) -> NaiveDate {
+    let ahead = (7 + day.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    today + Days::new(ahead as u64)
+}
+
+fn weekday(s: &str, allow_short: bool) -> Option<Weekday> {
+    let day = match s {
+        "monday" => Weekday::Mon,
+        "tuesday" => Weekday::Tue,
+        "wednesday" => Weekday::Wed,
+        "thursday" => Weekday::Thu,
+        "friday" => Weekday::Fri,
+        "saturday" => Weekday::Sat,
+        "sunday" => Weekday::Sun,
+        _ if !allow_short => return None,
+        "mon" => Weekday::Mon,
+        "tue" | "tues" => Weekday::Tue,
+        "wed" => Weekday::Wed,
+        "thu" | "thur" | "thurs" => Weekday::Thu,
+        "fri" => Weekday::Fri,
+        "sat" => Weekday::Sat,
+        "sun" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(day)
+}
+
+fn month(s: &str) -> Option<u32> {
+    let m = match s {
+        "jan" | "january" => 1,
+        "feb" | "february" => 2,
+        "mar" | "march" => 3,
+        "apr" | "april" => 4,
+        "may" => 5,
+        "jun" | "june" => 6,
+        "jul" | "july" => 7,
+        "aug" | "august" => 8,
+        "sep" | "sept" | "september" => 9,
+        "oct" | "october" => 10,
+        "nov" | "november" => 11,
+        "dec" | "december" => 12,
+        _ => return None,
+    };
+    Some(m)
+}
+
+fn day_of_month(s: &str) -> Option<u32> {
+    let digits = s.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let suffix = &s[digits.len()..];
+    if !matches!(suffix, "" | "st" | "nd" | "rd" | "th") || digits.len() > 2 {
+        return None;
+    }
+    digits.parse().ok().filter(|d| (1..=31).contains(d))
+}
+
+fn year(s: &str) -> Option<i32> {
+    if s.len() != 4 { return None; }
+    s.parse().ok().filter(|y| (2000..=2100).contains(y))
+}
+
+fn month_range(y: i32, m: u32) -> Option<(NaiveDate, NaiveDate)> {
+    let first = NaiveDate::from_ymd_opt(y, m, 1)?;
+    Some((first, first + Months::new(1) - Days::new(1)))
+}
+
+/// A month named without a year: this year's, or next year's once it is more than a month past.
+fn coming_month(m: u32, today: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+    match month_range(today.year(), m)? {
+        (_, last) if last + Days::new(31) < today => month_range(today.year() + 1, m),
+        this_year => Some(this_year),
+    }
+}
+
+/// Without an explicit year, a date more than a month in the past means next year's.
+fn resolve_day(y: Option<i32>, m: u32, d: u32, today: NaiveDate) -> Option<NaiveDate> {
+    if let Some(y) = y {
+        return NaiveDate::from_ymd_opt(y, m, d);
+    }
+    let date = NaiveDate::from_ymd_opt(today.year(), m, d)?;
+    if date + Days::new(31) < today {
+        return NaiveDate::from_ymd_opt(today.year() + 1, m, d);
+    }
+    Some(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn windows(text: &str, reference: &str) -> Vec<(NaiveDate, NaiveDate)> {
+        let extractor = DateExtractor::new("America/New_York").unwrap();
+        let tz: Tz = "America/New_York".parse().unwrap();
+        let reference = reference.parse::<DateTime<Utc>>().unwrap();
+        extractor
+            .extract_text(text, TextField::Title, reference)
+            .iter()
+            .map(|w| (w.start.with_timezone(&tz).date_naive(), w.end.with_timezone(&tz).date_naive()))
+            .collect()
+    }
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn end_of_named_month() {
+        let got = windows("Deal expected by end of December", "2025-06-10T15:00:00Z");
+        assert_eq!(got.len(), 1);
+        assert_eq!(got[0].1, day(2025, 12, 31));
+    }
+
+    #[test]
+    fn end_of_month_with_year() {
+        assert_eq!(windows("end of March 2026", "2025-06-10T15:00:00Z"), vec![(day(2026, 3, 31), day(2026, 3, 31))]);
+    }
+
+    #[test]
+    fn end_of_march_and_may() {
+        let reference = "2025-01-10T15:00:00Z";
+        assert_eq!(windows("end of March", reference), vec![(day(2025, 3, 31), day(2025, 3, 31))]);
+        assert_eq!(windows("end of May", reference), vec![(day(2025, 5, 31), day(2025, 5, 31))]);
+    }
+
+    #[test]
+    fn end_of_lowercase_month() {
+        assert_eq!(windows("end of june", "2025-08-20T15:00:00Z"), vec![(day(2026, 6, 30), day(2026, 6, 30))]);
+    }
+
+    #[test]
+    fn end_of_period_still_parses() {
+        assert_eq!(windows("end of the quarter", "2025-05-10T15:00:00Z"), vec![(day(2025, 6, 30), day(2025, 6, 30))]);
+    }
+
+    #[test]
+    fn past_quarter_rolls_forward() {
+        assert_eq!(windows("GDP in Q1", "2025-11-20T15:00:00Z"), vec![(day(2026, 1, 1), day(2026, 3, 31))]);
+    }
+
+    #[test]
+    fn current_quarter_stays() {
+        assert_eq!(windows("GDP in Q4", "2025-11-20T15:00:00Z"), vec![(day(2025, 10, 1), day(2025, 12, 31))]);
+    }
+
+    #[test]
+    fn past_half_rolls_forward() {
+        assert_eq!(windows("H1 earnings", "2025-09-01T15:00:00Z"), vec![(day(2026, 1, 1), day(2026, 6, 30))]);
+    }
+
+    #[test]
+    fn explicit_year_does_not_roll() {
+        assert_eq!(windows("Q1 2025 results", "2025-11-20T15:00:00Z"), vec![(day(2025, 1, 1), day(2025, 3, 31))]);
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+use aho_corasick::{AhoCorasick, MatchKind};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use xxhash_rust::xxh3::Xxh3;
+use crate::config::config::EntitiesCfg;
+use crate::text::normalizer::{NewsTokens, Normalizer, Token};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum EntityKind {
+    CentralBank,
+    Country,
+    Leader,
+    EconomicTerm,
+    Ticker,
+}
+
+impl EntityKind {
+    pub const ALL: [EntityKind; 5] = [
+        EntityKind::CentralBank,
+        EntityKind::Country,
+        EntityKind::Leader,
+        EntityKind::EconomicTerm,
+        EntityKind::Ticker,
+    ];
+}
+
+/// One line of a dictionary file.
+/// `aliases` match case-insensitively; `acronyms` only when written ALL-CAPS ("US" but not "us").
+/// `linked` tags a second entity on match, e.g. Powell -> FED; a row's `role` ("chair") is
+/// there for readers and not loaded.
+#[derive(Clone, Debug, Deserialize)]
+struct DictEntry {
+    id: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    acronyms: Vec<String>,
+    #[serde(default)]
+    linked: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Entities {
+    pub central_banks: BTreeSet<String>,
+    pub countries: BTreeSet<String>,
+    pub leaders: BTreeSet<String>,
+    pub economic_terms: BTreeSet<String>,
+    pub tickers: BTreeSet<String>,
+}
+
+impl Entities {
+    pub fn get(&self, kind: EntityKind) -> &BTreeSet<String> {
+        match kind {
+            EntityKind::CentralBank => &self.central_banks,
+            EntityKind::Country => &self.countries,
+            EntityKind::Leader => &self.leaders,
+            EntityKind::EconomicTerm => &self.economic_terms,
+            EntityKind::Ticker => &self.tickers,
+        }
+    }
+
+    fn insert(&mut self, kind: EntityKind, id: &str) {
+        let set = match kind {
+            EntityKind::CentralBank => &mut self.central_banks,
+            EntityKind::Country => &mut self.countries,
+            EntityKind::Leader => &mut self.leaders,
+            EntityKind::EconomicTerm => &mut self.economic_terms,
+            EntityKind::Ticker => &mut self.tickers,
+        };
+        set.insert(id.to_string());
+    }
+}
+
+struct Pattern {
+    entry: usize,
+    acronym: bool,
+}
+
+/// Dictionary-driven entity tagger. Aliases are pushed through the same `Normalizer` as the
+/// news, then matched leftmost-longest over the space-joined token stream.
+pub struct EntityExtractor {
+    cfg: EntitiesCfg,
+    /// Hash of the dictionary files as last read, to tell whether a reload changed anything.
+    digest: u64,
+    entries: Vec<(EntityKind, DictEntry)>,
+    kinds: HashMap<String, EntityKind>,
+    patterns: Vec<Pattern>,
+    matcher: AhoCorasick,
+}
+
+impl EntityExtractor {
+    pub fn load(cfg: &EntitiesCfg) -> Result<Self> {
+        let normalizer = Normalizer::default();
+        let mut entries = Vec::new();
+        let mut digest = Xxh3::new();
+        for dict in &cfg.dictionaries {
+            let raw = std::fs::read_to_string(&dict.path)
+                .with_context(|| format!("reading entity dictionary {}", dict.path))?;
+            digest.update(raw.as_bytes());
+            let rows: Vec<DictEntry> = serde_json::from_str(&raw)
+                .with_context(|| format!("parsing entity dictionary {}", dict.path))?;
+            entries.extend(rows.into_iter().map(|e| (dict.kind, e)));
+        }
+
+        let kinds = entries.iter().map(|(k, e)| (e.id.clone(), *k)).collect();
+
+        let mut needles = Vec::new();
+        let mut patterns = Vec::new();
+        for (i, (_, e)) in entries.iter().enumerate() {
+            let forms = e.aliases.iter().map(|a| (a, false)).chain(e.acronyms.iter().map(|a| (a, true)));
+            for (form, acronym) in forms {
+                let toks = normalizer.tokenize(form);
+                if toks.is_empty() { continue; }
+                needles.push(join(&toks));
+                patterns.push(Pattern { entry: i, acronym });
+            }
+        }
+
+        let matcher = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(&needles)
+            .context("building entity matcher")?;
+
+        Ok(Self { cfg: cfg.clone(), digest: digest.digest(), entries, kinds, patterns, matcher })
+    }
+
+    /// Re-read the dictionary files; `true` if they changed. On failure the current
+    /// dictionaries stay in place.
+    pub fn reload(&mut self) -> Result<bool> {
+        let next = Self::load(&self.cfg)?;
+        let changed = next.digest != self.digest;
+        *self = next;
+        Ok(changed)
+    }
+
+    /// How often the owner should call `reload`.
+    pub fn reload_interval(&self) -> Duration {
+        self.cfg.reload
+    }
+
+    pub fn extract(&self, tokens: &NewsTokens) -> Entities {
+        let mut out = Entities::default();
+        for (_, toks) in tokens.fields() {
+            self.extract_field(toks, &mut out);
+        }
+        out
+    }
+
+    fn extract_field(&self, toks: &[Token], out: &mut Entities) {
+        if toks.is_empty() { return; }
+
+        // token i spans hay[starts[i]..starts[i] + toks[i].text.len()]
+        let hay = join(toks);
+        let mut starts = Vec::with_capacity(toks.len());
+        let mut pos = 0;
+        for t in toks {
+            starts.push(pos);
+            pos += t.text.len() + 1;
+        }
+
+        for m in self.matcher.find_iter(&hay) {
+            // only whole-token matches
+            let Ok(first) = starts.binary_search(&m.start()) else { continue };
+            let Some(last) = (first..toks.len()).find(|&j| starts[j] + toks[j].text.len() == m.end()) else { continue };
+
+            let pattern = &self.patterns[m.pattern().as_usize()];
+            if pattern.acronym && !toks[first..=last].iter().all(|t| t.upper) {
+                continue;
+            }
+
+            let (kind, entry) = &self.entries[pattern.entry];
+            out.insert(*kind, &entry.id);
+            if let Some(linked) = &entry.linked
+                && let Some(linked_kind) = self.kinds.get(linked)
+            {
+                out.insert(*linked_kind, linked);
+            }
+        }
+    }
+}
+
+fn join(toks: &[Token]) -> String {
+    toks.iter().map(|t| t.text.as_str()).collect::<Vec<_>>().join(" ")
+}
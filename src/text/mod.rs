@@ -0,0 +1,4 @@
+pub mod normalizer;
+pub mod entities;
+pub mod numbers;
+pub mod dates;
@@ -0,0 +1,192 @@
+use std::collections::HashSet;
+use crate::core::types::RawNews;
+
+// Minimal list on purpose: anything carrying meaning ("cut", "hike", "no", "not") must survive.
+const STOPWORDS: &[&str] = &[
+    "a", "an", "the", "and", "or", "to", "of", "in", "on", "at", "for", "by", "with", "from",
+    "as", "is", "are", "was", "were", "be", "been", "it", "its", "this", "that", "these",
+    "those", "will",
+];
+
+/// A normalized token. `start..end` is the byte range of the token in the source text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub text: String,
+    /// Token was written ALL-CAPS in the source (FOMC, CPI, US, Q4).
+    pub upper: bool,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextField {
+    Title,
+    Description,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct NewsTokens {
+    pub title: Vec<Token>,
+    pub description: Vec<Token>,
+}
+
+impl NewsTokens {
+    pub fn iter(&self) -> impl Iterator<Item = &Token> {
+        self.title.iter().chain(self.description.iter())
+    }
+
+    pub fn fields(&self) -> [(TextField, &[Token]); 2] {
+        [(TextField::Title, &self.title), (TextField::Description, &self.description)]
+    }
+}
+
+/// Shared tokenizer for headlines and market text, so both sides of the matcher agree on terms.
+#[derive(Clone, Debug)]
+pub struct Normalizer {
+    stopwords: HashSet<&'static str>,
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Self { stopwords: STOPWORDS.iter().copied().collect() }
+    }
+}
+
+impl Normalizer {
+    pub fn tokenize_news(&self, news: &RawNews) -> NewsTokens {
+        NewsTokens {
+            title: self.tokenize(&news.title),
+            description: self.tokenize(&news.description),
+        }
+    }
+
+    /// Lowercase, drop URLs / markup / punctuation / emoji, keep numbers (3.2%, 1,000 -> 1000)
+    /// and flag ALL-CAPS words. Stopwords are dropped unless written ALL-CAPS ("IT", "US").
+    pub fn tokenize(&self, text: &str) -> Vec<Token> {
+        let mut out = Vec::new();
+        let mut i = 0;
+
+        while i < text.len() {
+            let rest = &text[i..];
+            let c = rest.chars().next().unwrap();
+
+            if starts_with_url(rest) {
+                i += rest.find(char::is_whitespace).unwrap_or(rest.len());
+                continue;
+            }
+            if let Some(skip) = markup_len(rest) {
+                i += skip;
+                continue;
+            }
+            if !c.is_alphanumeric() {
+                i += c.len_utf8();
+                continue;
+            }
+
+            let (end, next) = dotted_acronym_len(rest)
+                .map(|n| (i + n, i + n))
+                .unwrap_or_else(|| scan_word(text, i));
+            if let Some(tok) = self.make_token(&text[i..end], i, end) {
+                out.push(tok);
+            }
+            i = next;
+        }
+        out
+    }
+
+    fn make_token(&self, word: &str, start: usize, end: usize) -> Option<Token> {
+        let upper = word.chars().count() >= 2
+            && word.chars().any(char::is_alphabetic)
+            && !word.chars().any(char::is_lowercase);
+
+        // Decimal points survive, thousands separators / apostrophes / acronym dots do not.
+        let mut text = String::with_capacity(word.len());
+        let mut prev = ' ';
+        for c in word.chars() {
+            let keep = match c {
+                ',' | '\'' | '’' => false,
+                '.' => prev.is_ascii_digit(),
+                _ => true,
+            };
+            if keep {
+                text.extend(c.to_lowercase());
+            }
+            prev = c;
+        }
+        if !upper && self.stopwords.contains(text.as_str()) {
+            return None;
+        }
+        Some(Token { text, upper, start, end })
+    }
+}
+
+fn starts_with_url(s: &str) -> bool {
+    let prefixed = |p: &str| s.get(..p.len()).is_some_and(|h| h.eq_ignore_ascii_case(p));
+    prefixed("http://") || prefixed("https://") || prefixed("www.")
+}
+
+// RSS descriptions carry HTML tags and entities; skip them whole.
+fn markup_len(s: &str) -> Option<usize> {
+    let mut chars = s.chars();
+    match chars.next()? {
+        '<' => {
+            let next = chars.next()?;
+            if !(next.is_ascii_alphabetic() || next == '/' || next == '!') {
+                return None;
+            }
+            s.find('>').map(|j| j + 1)
+        }
+        '&' => {
+            let j = s.get(..10).unwrap_or(s).find(';')?;
+            s[1..j].chars().all(|c| c.is_ascii_alphanumeric() || c == '#').then_some(j + 1)
+        }
+        _ => None,
+    }
+}
+
+// "U.S." / "U.K." -> one token spelled without dots.
+fn dotted_acronym_len(s: &str) -> Option<usize> {
+    let b = s.as_bytes();
+    let mut n = 0;
+    while n + 1 < b.len() && b[n].is_ascii_alphabetic() && b[n + 1] == b'.' {
+        n += 2;
+    }
+    let boundary = b.get(n).is_none_or(|c| !c.is_ascii_alphanumeric());
+    (n >= 4 && boundary).then_some(n)
+}
+
+/// Returns (end of token, position to resume scanning). A possessive "'s" is consumed but not
+/// kept, other apostrophes are folded into the word ("don't" -> "dont"); '.' and ',' are kept
+/// between digits and a trailing '%' sticks to its number.
+fn scan_word(text: &str, start: usize) -> (usize, usize) {
+    let mut end = start;
+    let mut prev_digit = false;
+    let mut iter = text[start..].char_indices().peekable();
+
+    while let Some((off, c)) = iter.next() {
+        let pos = start + off;
+        if c.is_alphanumeric() {
+            prev_digit = c.is_ascii_digit();
+            end = pos + c.len_utf8();
+            continue;
+        }
+        let next = iter.peek().map(|&(_, n)| n);
+        match c {
+            '.' | ',' if prev_digit && next.is_some_and(|n| n.is_ascii_digit()) => {
+                end = pos + 1;
+            }
+            '%' if prev_digit => return (pos + 1, pos + 1),
+            '\'' | '’' if next.is_some_and(char::is_alphabetic) => {
+                let after = pos + c.len_utf8() + 1;
+                let possessive = matches!(next, Some('s') | Some('S'))
+                    && text[after..].chars().next().is_none_or(|n| !n.is_alphanumeric());
+                if possessive {
+                    return (end, after);
+                }
+                end = pos + c.len_utf8();
+            }
+            _ => break,
+        }
+    }
+    (end, end)
+}
@@ -0,0 +1,201 @@
+use crate::core::types::RawNews;
+use crate::text::normalizer::TextField;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Magnitude {
+    Units,
+    Thousand,
+    Million,
+    Billion,
+    Trillion,
+}
+
+impl Magnitude {
+    fn factor(self) -> f64 {
+        match self {
+            Magnitude::Units => 1.0,
+            Magnitude::Thousand => 1e3,
+            Magnitude::Million => 1e6,
+            Magnitude::Billion => 1e9,
+            Magnitude::Trillion => 1e12,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum QuantityKind {
+    /// 3.2% -> 3.2
+    Percent(f64),
+    /// 25bps -> 25.0
+    BasisPoints(f64),
+    /// $5B -> amount 5e9. `currency` is None for bare magnitudes like "1.2T".
+    Currency { amount: f64, currency: Option<&'static str>, magnitude: Magnitude },
+    Year(i32),
+    Integer(i64),
+    Decimal(f64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quantity {
+    pub kind: QuantityKind,
+    pub field: TextField,
+    /// Byte range in the source field, including sign, currency symbol and unit.
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Quantity {
+    /// Percent and basis points on one scale, so "50bps" and "0.5%" compare equal.
+    pub fn as_percent(&self) -> Option<f64> {
+        match self.kind {
+            QuantityKind::Percent(p) => Some(p),
+            QuantityKind::BasisPoints(bp) => Some(bp / 100.0),
+            _ => None,
+        }
+    }
+}
+
+pub fn extract_quantities(news: &RawNews) -> Vec<Quantity> {
+    let mut out = extract(&news.title, TextField::Title);
+    out.extend(extract(&news.description, TextField::Description));
+    out
+}
+
+// Suffixes are tried in order, so longer spellings must come first.
+const PERCENT: &[&str] = &["%", " percent", " pct", " per cent"];
+const BPS: &[&str] = &["bps", "bp", " bps", " bp", " basis points", " basis point"];
+const MAGNITUDES: &[(&str, Magnitude)] = &[
+    (" trillion", Magnitude::Trillion),
+    (" billion", Magnitude::Billion),
+    (" million", Magnitude::Million),
+    (" thousand", Magnitude::Thousand),
+    ("trn", Magnitude::Trillion),
+    ("tn", Magnitude::Trillion),
+    ("bln", Magnitude::Billion),
+    ("bn", Magnitude::Billion),
+    ("mln", Magnitude::Million),
+    ("mn", Magnitude::Million),
+    ("T", Magnitude::Trillion),
+    ("B", Magnitude::Billion),
+    ("b", Magnitude::Billion),
+    ("M", Magnitude::Million),
+    ("m", Magnitude::Million),
+    ("K", Magnitude::Thousand),
+    ("k", Magnitude::Thousand),
+];
+
+pub fn extract(text: &str, field: TextField) -> Vec<Quantity> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        // skip digits glued to letters on the left: Q4, H1, G7, S&P500 stays a token not a number
+        let prev = text[..i].chars().next_back();
+        let num_end = scan_number(bytes, i);
+        if prev.is_some_and(char::is_alphanumeric) {
+            i = num_end;
+            continue;
+        }
+
+        let (start, currency, negative) = prefix(text, i);
+        let literal = &text[i..num_end];
+        let Ok(mut value) = literal.replace(',', "").parse::<f64>() else {
+            i = num_end;
+            continue;
+        };
+        if negative { value = -value; }
+
+        let rest = &text[num_end..];
+        let (kind, end) = if let Some(n) = suffix(rest, PERCENT) {
+            (QuantityKind::Percent(value), num_end + n)
+        } else if let Some(n) = suffix(rest, BPS) {
+            (QuantityKind::BasisPoints(value), num_end + n)
+        } else if let Some((n, magnitude)) = MAGNITUDES.iter().find_map(|(s, m)| suffix(rest, &[s]).map(|n| (n, *m))) {
+            (QuantityKind::Currency { amount: value * magnitude.factor(), currency, magnitude }, num_end + n)
+        } else if currency.is_some() {
+            (QuantityKind::Currency { amount: value, currency, magnitude: Magnitude::Units }, num_end)
+        } else if rest.chars().next().is_some_and(char::is_alphanumeric) {
+            // 10y, 3rd, 2x: unit we don't model
+            i = num_end;
+            continue;
+        } else if literal.contains('.') {
+            (QuantityKind::Decimal(value), num_end)
+        } else if literal.len() == 4 && (1900.0..=2100.0).contains(&value) && !negative {
+            (QuantityKind::Year(value as i32), num_end)
+        } else {
+            (QuantityKind::Integer(value as i64), num_end)
+        };
+
+        out.push(Quantity { kind, field, start, end });
+        i = end;
+    }
+    out
+}
+
+/// End of a number literal: digits with ',' thousand groups and one '.' decimal part.
+fn scan_number(bytes: &[u8], start: usize) -> usize {
+    let mut end = start;
+    let mut seen_dot = false;
+    while end < bytes.len() {
+        let c = bytes[end];
+        let next_digit = bytes.get(end + 1).is_some_and(u8::is_ascii_digit);
+        match c {
+            b'0'..=b'9' => end += 1,
+            b',' if next_digit && !seen_dot => end += 1,
+            b'.' if next_digit && !seen_dot => {
+                seen_dot = true;
+                end += 1;
+            }
+            _ => break,
+        }
+    }
+    end
+}
+
+/// Currency symbol and sign directly in front of the number: "-$5B", "US$3", "€1.2bn".
+fn prefix(text: &str, num_start: usize) -> (usize, Option<&'static str>, bool) {
+    let mut start = num_start;
+    let mut currency = None;
+
+    if let Some(c) = text[..start].chars().next_back() {
+        currency = match c {
+            '$' => Some("USD"),
+            '€' => Some("EUR"),
+            '£' => Some("GBP"),
+            '¥' => Some("JPY"),
+            _ => None,
+        };
+        if currency.is_some() {
+            start -= c.len_utf8();
+            if text[..start].ends_with("US") {
+                start -= 2;
+            }
+        }
+    }
+
+    let negative = text[..start].ends_with('-')
+        && text[..start - 1].chars().next_back().is_none_or(char::is_whitespace);
+    if negative {
+        start -= 1;
+    }
+    (start, currency, negative)
+}
+
+/// Length of the first matching unit suffix, which must end on a word boundary.
+fn suffix(rest: &str, units: &[&str]) -> Option<usize> {
+    units.iter().find_map(|u| {
+        // single letters are case-sensitive (M = million, K = thousand), spelled units are not
+        let matched = if u.len() > 1 {
+            rest.get(..u.len()).is_some_and(|h| h.eq_ignore_ascii_case(u))
+        } else {
+            rest.starts_with(u)
+        };
+        let boundary = || rest[u.len()..].chars().next().is_none_or(|c| !c.is_alphanumeric());
+        (matched && boundary()).then_some(u.len())
+    })
+}
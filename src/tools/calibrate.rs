@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use crate::config::config::{CalibrationCfg, CALIBRATION_PATH};
+use crate::strategy::probability::{shrunk_logistic, sigmoid};
+
+const LAMBDA_MIN: f64 = 0.2;
+const LAMBDA_MAX: f64 = 0.6;
+const LAMBDA_STEP: f64 = 0.01;
+const RELIABILITY_BINS: usize = 10;
+const EPS: f64 = 1e-12;
+
+#[derive(Debug, Deserialize)]
+struct Record {
+    score: f64,
+    #[serde(default)]
+    category: Option<String>,
+    #[serde(deserialize_with = "de_outcome")]
+    outcome: f64,
+}
+
+struct Args {
+    input: String,
+    output: String,
+    min_samples: usize,
+}
+
+struct Fit {
+    params: CalibrationCfg,
+    brier: f64,
+    log_loss: f64,
+    /// (mean predicted, observed rate, count) per probability bin.
+    reliability: Vec<(f64, f64, usize)>,
+}
+
+/// `calibrate --input <records.jsonl> [--output calibration.yml] [--min-samples 50]`
+///
+/// Fits (a, b, λ) by maximum likelihood from resolved records, one per line:
+/// `{"score": 0.62, "market": "512345", "category": "Fed Rates", "outcome": true}`.
+/// All records fit the default parameters; categories with at least `--min-samples` records
+/// also get their own. The output is in config layout and overrides config.yml on next start.
+pub fn run(args: &[String]) -> Result<()> {
+    let args = parse_args(args)?;
+    let raw = std::fs::read_to_string(&args.input).with_context(|| format!("reading {}", args.input))?;
+    let mut records = Vec::new();
+    for (i, line) in raw.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        let r: Record = serde_json::from_str(line).with_context(|| format!("{}:{}", args.input, i + 1))?;
+        anyhow::ensure!(r.score.is_finite(), "{}:{}: score must be finite", args.input, i + 1);
+        records.push(r);
+    }
+    anyhow::ensure!(!records.is_empty(), "{} has no records", args.input);
+
+    let all: Vec<(f64, f64)> = records.iter().map(|r| (r.score, r.outcome)).collect();
+    let default = fit(&all);
+    report("default", all.len(), &default);
+
+    let mut by_category: BTreeMap<&str, Vec<(f64, f64)>> = BTreeMap::new();
+    for r in &records {
+        if let Some(c) = &r.category {
+            by_category.entry(c.as_str()).or_default().push((r.score, r.outcome));
+        }
+    }
+    let mut categories = BTreeMap::new();
+    for (category, samples) in by_category {
+        if samples.len() < args.min_samples {
+            println!("\n{category}: {} records < --min-samples {}, using default", samples.len(), args.min_samples);
+            continue;
+        }
+        let f = fit(&samples);
+        report(category, samples.len(), &f);
+        categories.insert(category, f.params);
+    }
+
+    std::fs::write(&args.output, render(&default.params, &categories))
+        .with_context(|| format!("writing {}", args.output))?;
+    println!("\nwrote {}", args.output);
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<Args> {
+    let mut input = None;
+    let mut output = CALIBRATION_PATH.to_string();
+    let mut min_samples = 50;
+    let mut it = args.iter();
+    while let Some(flag) = it.next() {
+        let mut value = || it.next().with_context(|| format!("{flag} needs a value"));
+        match flag.as_str() {
+            "--input" => input = Some(value()?.clone()),
+            "--output" => output = value()?.clone(),
+            "--min-samples" => min_samples = value()?.parse().context("--min-samples")?,
+            other => bail!("unknown argument {other}; usage: calibrate --input <records.jsonl> [--output <file>] [--min-samples <n>]"),
+        }
+    }
+    let input = input.context("--input is required")?;
+    Ok(Args { input, output, min_samples })
+}
+
+/// Maximum likelihood over a λ grid; (a, b) by Fisher scoring for each λ.
+fn fit(samples: &[(f64, f64)]) -> Fit {
+    let mut best: Option<(f64, CalibrationCfg)> = None;
+    let steps = ((LAMBDA_MAX - LAMBDA_MIN) / LAMBDA_STEP).round() as usize;
+    for i in 0..=steps {
+        let lambda = LAMBDA_MIN + i as f64 * LAMBDA_STEP;
+        let (a, b) = fit_ab(samples, lambda);
+        let params = CalibrationCfg { a, b, lambda };
+        let ll = log_likelihood(samples, &params);
+        if best.as_ref().is_none_or(|(l, _)| ll > *l) {
+            best = Some((ll, params));
+        }
+    }
+    let (ll, params) = best.expect("lambda grid is not empty");
+
+    let n = samples.len() as f64;
+    let brier = samples.iter().map(|&(s, y)| (shrunk_logistic(&params, s) - y).powi(2)).sum::<f64>() / n;
+    let mut bins = vec![(0.0, 0.0, 0usize); RELIABILITY_BINS];
+    for &(s, y) in samples {
+        let p = shrunk_logistic(&params, s);
+        let bin = &mut bins[((p * RELIABILITY_BINS as f64) as usize).min(RELIABILITY_BINS - 1)];
+        bin.0 += p;
+        bin.1 += y;
+        bin.2 += 1;
+    }
+    let reliability = bins
+        .into_iter()
+        .map(|(p, y, c)| if c == 0 { (0.0, 0.0, 0) } else { (p / c as f64, y / c as f64, c) })
+        .collect();
+    Fit { params, brier, log_loss: -ll / n, reliability }
+}
+
+fn fit_ab(samples: &[(f64, f64)], lambda: f64) -> (f64, f64) {
+    let (mut a, mut b) = (0.0, 0.0);
+    for _ in 0..100 {
+        // gradient and Fisher information of the log-likelihood in (a, b)
+        let (mut ga, mut gb, mut iaa, mut iab, mut ibb) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for &(s, y) in samples {
+            let q = sigmoid(a + b * s);
+            let p = (0.5 + lambda * (q - 0.5)).clamp(EPS, 1.0 - EPS);
+            let dp = lambda * q * (1.0 - q);
+            let g = (y - p) / (p * (1.0 - p)) * dp;
+            let w = dp * dp / (p * (1.0 - p));
+            ga += g;
+            gb += g * s;
+            iaa += w;
+            iab += w * s;
+            ibb += w * s * s;
+        }
+        // small ridge keeps separable data from diverging
+        iaa += 1e-6;
+        ibb += 1e-6;
+        let det = iaa * ibb - iab * iab;
+        if det.abs() < EPS {
+            break;
+        }
+        let da = (ibb * ga - iab * gb) / det;
+        let db = (iaa * gb - iab * ga) / det;
+        a = (a + da).clamp(-50.0, 50.0);
+        b = (b + db).clamp(-50.0, 50.0);
+        if da.abs() + db.abs() < 1e-9 {
+            break;
+        }
+    }
+    (a, b)
+}
+
+fn log_likelihood(samples: &[(f64, f64)], params: &CalibrationCfg) -> f64 {
+    samples
+        .iter()
+        .map(|&(s, y)| {
+            let p = shrunk_logistic(params, s).clamp(EPS, 1.0 - EPS);
+            y * p.ln() + (1.0 - y) * (1.0 - p).ln()
+        })
+        .sum()
+}
+
+fn report(name: &str, n: usize, f: &Fit) {
+    let p = &f.params;
+    println!("\n{name}: {n} records");
+    println!("  a = {:.4}  b = {:.4}  lambda = {:.2}", p.a, p.b, p.lambda);
+    println!("  brier = {:.4}  log-loss = {:.4}", f.brier, f.log_loss);
+    println!("  {:>9}  {:>9}  {:>9}  {:>6}", "bin", "predicted", "observed", "count");
+    for (i, (pred, obs, count)) in f.reliability.iter().enumerate() {
+        if *count == 0 { continue; }
+        let lo = i as f64 / RELIABILITY_BINS as f64;
+        let hi = lo + 1.0 / RELIABILITY_BINS as f64;
+        println!("  {lo:.1}-{hi:.1}    {pred:>9.3}  {obs:>9.3}  {count:>6}");
+    }
+}
+
+fn render(default: &CalibrationCfg, categories: &BTreeMap<&str, CalibrationCfg>) -> String {
+    let line = |c: &CalibrationCfg| format!("{{ a: {:.6}, b: {:.6}, lambda: {:.2} }}", c.a, c.b, c.lambda);
+    let mut out = String::from("# written by `ingestor calibrate`; overrides strategy.probability in config.yml\n");
+    out.push_str("strategy:\n  probability:\n");
+    let _ = writeln!(out, "    default: {}", line(default));
+    if !categories.is_empty() {
+        out.push_str("    categories:\n");
+        for (name, c) in categories {
+            // JSON strings are valid YAML scalars
+            let _ = writeln!(out, "      {}: {}", serde_json::Value::from(*name), line(c));
+        }
+    }
+    out
+}
+
+fn de_outcome<'de, D: serde::Deserializer<'de>>(d: D) -> std::result::Result<f64, D::Error> {
+    use serde::de::Error;
+    match serde_json::Value::deserialize(d)? {
+        serde_json::Value::Bool(b) => Ok(if b { 1.0 } else { 0.0 }),
+        serde_json::Value::Number(n) if n.as_f64() == Some(0.0) || n.as_f64() == Some(1.0) => Ok(n.as_f64().unwrap()),
+        serde_json::Value::String(s) if s.eq_ignore_ascii_case("yes") => Ok(1.0),
+        serde_json::Value::String(s) if s.eq_ignore_ascii_case("no") => Ok(0.0),
+        other => Err(D::Error::custom(format!("outcome must be true/false, 1/0 or \"Yes\"/\"No\", got {other}"))),
+    }
+}
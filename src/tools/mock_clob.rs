@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use anyhow::{bail, Context, Result};
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use tracing::info;
+use crate::config::config::AppCfg;
+use crate::execution::clob::{
+    from_units, l2_signature, ApiCreds, BalanceAllowance, CancelRequest, CancelResponse, OpenOrder, PostOrderRequest,
+    PostOrderResponse,
+};
+use crate::execution::eip712::{self, Address, SIDE_BUY};
+
+/// L2 timestamps further than this from the server clock are refused.
+const MAX_CLOCK_SKEW_SECS: u64 = 30;
+
+struct MockOrder {
+    status: &'static str,
+    original_size: f64,
+    size_matched: f64,
+    price: f64,
+    side: String,
+    asset_id: String,
+}
+
+impl MockOrder {
+    fn buy(&self) -> bool {
+        self.side == "BUY"
+    }
+}
+
+#[derive(Default)]
+struct Book {
+    orders: HashMap<String, MockOrder>,
+    cash: f64,
+    /// By token id.
+    tokens: HashMap<String, f64>,
+}
+
+impl Book {
+    /// Matches the rest of an order and settles it against the account.
+    fn match_order(&mut self, id: &str) {
+        let Some(o) = self.orders.get_mut(id) else { return };
+        let size = o.original_size - o.size_matched;
+        o.size_matched = o.original_size;
+        o.status = "MATCHED";
+        let signed = if o.buy() { size } else { -size };
+        self.cash -= signed * o.price;
+        *self.tokens.entry(o.asset_id.clone()).or_default() += signed;
+    }
+}
+
+struct Mock {
+    creds: ApiCreds,
+    chain_id: u64,
+    exchanges: [Address; 2],
+    book: Mutex<Book>,
+}
+
+type Rejection = (StatusCode, Json<serde_json::Value>);
+
+/// `mock-clob [--port 8081] [--config config.yml] [--balance 1000]`
+///
+/// A local stand-in for the CLOB REST API using the credentials in `execution.clob`. It
+/// checks the L2 HMAC headers and that each order's EIP-712 signature recovers to its signer
+/// and the calling address. FOK/FAK orders match in full on arrival; GTC/GTD orders rest and
+/// match in full on the first status poll. Matches settle against a single account starting
+/// with `--balance` USDC.
+pub async fn run(args: &[String]) -> Result<()> {
+    let mut port = 8081u16;
+    let mut config = "config.yml".to_string();
+    let mut balance = 1000.0;
+    let mut it = args.iter();
+    while let Some(flag) = it.next() {
+        let mut value = || it.next().with_context(|| format!("{flag} needs a value"));
+        match flag.as_str() {
+            "--port" => port = value()?.parse().context("--port")?,
+            "--config" => config = value()?.clone(),
+            "--balance" => balance = value()?.parse().context("--balance")?,
+            other => bail!("unknown argument {other}; usage: mock-clob [--port <port>] [--config <file>] [--balance <usdc>]"),
+        }
+    }
+    let cfg = AppCfg::load(&config)?;
+    let clob = cfg.execution.clob.as_ref().context("execution.clob missing")?;
+    let mock = Arc::new(Mock {
+        creds: ApiCreds::resolve(clob)?,
+        chain_id: clob.chain_id,
+        exchanges: [eip712::parse_address(&clob.exchange)?, eip712::parse_address(&clob.neg_risk_exchange)?],
+        book: Mutex::new(Book { cash: balance, ..Default::default() }),
+    });
+
+    let app = Router::new()
+        .route("/order", post(place).delete(cancel))
+        .route("/data/order/{id}", get(order))
+        .route("/balance-allowance", get(balance_allowance))
+        .with_state(mock);
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await.with_context(|| format!("binding port {port}"))?;
+    info!(port, "mock CLOB listening");
+    axum::serve(listener, app).await.context("serving")
+}
+
+async fn place(State(mock): State<Arc<Mock>>, headers: HeaderMap, body: String) -> Result<Json<PostOrderResponse>, Rejection> {
+    let caller = mock.authenticate(&headers, "POST", "/order", &body).map_err(unauthorized)?;
+    let req: PostOrderRequest = serde_json::from_str(&body).map_err(bad_request)?;
+    let fill_now = matches!(req.order_type.as_str(), "FOK" | "FAK");
+    let order = match mock.verify(&req, &caller) {
+        Ok(order) => order,
+        Err(e) => {
+            return Ok(Json(PostOrderResponse { success: false, error_msg: format!("{e:#}"), ..Default::default() }));
+        }
+    };
+    // buys give USDC for tokens, sells the reverse
+    let (usdc, tokens) = if order.side == SIDE_BUY {
+        (order.maker_amount, order.taker_amount)
+    } else {
+        (order.taker_amount, order.maker_amount)
+    };
+    let (usdc, tokens) = (from_units(usdc), from_units(tokens));
+
+    let mut book = mock.book.lock().expect("book lock");
+    let id = format!("0x{:064x}", book.orders.len() + 1);
+    book.orders.insert(id.clone(), MockOrder {
+        status: "LIVE",
+        original_size: tokens,
+        size_matched: 0.0,
+        price: usdc / tokens,
+        side: req.order.side.clone(),
+        asset_id: req.order.token_id.clone(),
+    });
+    if fill_now {
+        book.match_order(&id);
+    }
+    info!(order_id = %id, order_type = %req.order_type, side = %req.order.side, tokens, usdc, "mock order accepted");
+
+    let (making, taking) = if order.side == SIDE_BUY { (usdc, tokens) } else { (tokens, usdc) };
+    Ok(Json(PostOrderResponse {
+        success: true,
+        error_msg: String::new(),
+        order_id: id,
+        status: if fill_now { "matched" } else { "live" }.to_string(),
+        making_amount: if fill_now { making.to_string() } else { String::new() },
+        taking_amount: if fill_now { taking.to_string() } else { String::new() },
+    }))
+}
+
+async fn cancel(State(mock): State<Arc<Mock>>, headers: HeaderMap, body: String) -> Result<Json<CancelResponse>, Rejection> {
+    mock.authenticate(&headers, "DELETE", "/order", &body).map_err(unauthorized)?;
+    let req: CancelRequest = serde_json::from_str(&body).map_err(bad_request)?;
+    let mut resp = CancelResponse::default();
+    match mock.book.lock().expect("book lock").orders.get_mut(&req.order_id) {
+        Some(o) if o.status == "LIVE" => {
+            o.status = "CANCELED";
+            resp.canceled.push(req.order_id);
+        }
+        Some(_) => {
+            resp.not_canceled.insert(req.order_id, "order is not live".to_string());
+        }
+        None => {
+            resp.not_canceled.insert(req.order_id, "order not found".to_string());
+        }
+    }
+    Ok(Json(resp))
+}
+
+async fn order(State(mock): State<Arc<Mock>>, Path(id): Path<String>, headers: HeaderMap) -> Result<Json<OpenOrder>, Rejection> {
+    mock.authenticate(&headers, "GET", &format!("/data/order/{id}"), "").map_err(unauthorized)?;
+    let mut book = mock.book.lock().expect("book lock");
+    // canned fill: resting orders match on the first poll
+    if book.orders.get(&id).ok_or_else(|| bad_request("order not found"))?.status == "LIVE" {
+        book.match_order(&id);
+    }
+    let o = &book.orders[&id];
+    Ok(Json(OpenOrder {
+        id,
+        status: o.status.to_string(),
+        original_size: o.original_size.to_string(),
+        size_matched: o.size_matched.to_string(),
+        price: o.price.to_string(),
+        side: o.side.clone(),
+        asset_id: o.asset_id.clone(),
+    }))
+}
+
+async fn balance_allowance(
+    State(mock): State<Arc<Mock>>,
+    Query(query): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Result<Json<BalanceAllowance>, Rejection> {
+    mock.authenticate(&headers, "GET", "/balance-allowance", "").map_err(unauthorized)?;
+    let book = mock.book.lock().expect("book lock");
+    let balance = match (query.get("asset_type").map(String::as_str), query.get("token_id")) {
+        (Some("COLLATERAL"), _) => book.cash,
+        (Some("CONDITIONAL"), Some(token_id)) => book.tokens.get(token_id).copied().unwrap_or(0.0),
+        _ => return Err(bad_request("asset_type must be COLLATERAL, or CONDITIONAL with a token_id")),
+    };
+    let units = (balance.max(0.0) * 1e6).round() as u128;
+    Ok(Json(BalanceAllowance { balance: units.to_string(), allowance: units.to_string() }))
+}
+
+impl Mock {
+    /// Checks the L2 headers; returns the calling address.
+    fn authenticate(&self, headers: &HeaderMap, method: &str, path: &str, body: &str) -> Result<Address> {
+        let header = |name: &str| {
+            headers.get(name).and_then(|v| v.to_str().ok()).with_context(|| format!("missing {name} header"))
+        };
+        anyhow::ensure!(header("POLY_API_KEY")? == self.creds.key, "unknown api key");
+        anyhow::ensure!(header("POLY_PASSPHRASE")? == self.creds.passphrase, "wrong passphrase");
+        let timestamp: u64 = header("POLY_TIMESTAMP")?.parse().context("POLY_TIMESTAMP")?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        anyhow::ensure!(now.abs_diff(timestamp) <= MAX_CLOCK_SKEW_SECS, "stale POLY_TIMESTAMP");
+        let expected = l2_signature(&self.creds.secret, timestamp, method, path, body)?;
+        anyhow::ensure!(header("POLY_SIGNATURE")? == expected, "bad POLY_SIGNATURE");
+        eip712::parse_address(header("POLY_ADDRESS")?)
+    }
+
+    /// The order's signature must recover to its signer, who must be the caller.
+    fn verify(&self, req: &PostOrderRequest, caller: &Address) -> Result<eip712::ClobOrder> {
+        anyhow::ensure!(req.owner == self.creds.key, "owner is not the api key");
+        let order = req.order.to_order()?;
+        anyhow::ensure!(&order.signer == caller, "signer is not POLY_ADDRESS");
+        if order.signature_type == 0 {
+            anyhow::ensure!(order.maker == order.signer, "EOA orders must be made by their signer");
+        }
+        anyhow::ensure!(order.maker_amount > 0 && order.taker_amount > 0, "amounts must be > 0");
+        let signed = self.exchanges.iter().any(|exchange| {
+            order
+                .digest(self.chain_id, exchange)
+                .and_then(|digest| eip712::recover(&digest, &req.order.signature))
+                .is_ok_and(|signer| signer == order.signer)
+        });
+        anyhow::ensure!(signed, "invalid order signature");
+        Ok(order)
+    }
+}
+
+fn unauthorized(e: anyhow::Error) -> Rejection {
+    (StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": format!("{e:#}") })))
+}
+
+fn bad_request(e: impl std::fmt::Display) -> Rejection {
+    (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e.to_string() })))
+}
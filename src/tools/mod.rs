@@ -0,0 +1,2 @@
+pub mod calibrate;
+pub mod mock_clob;